@@ -1,20 +1,25 @@
-use hyperborealib::crypto::asymmetric::SecretKey;
+use hyperborealib::crypto::asymmetric::{SecretKey, PublicKey};
 
-use hyperborealib::time::timestamp;
 use hyperborealib::crypto::utils::safe_random_u64;
 
+use crate::clock::{Clock, SystemClock};
+use crate::signer::Signer;
+
 use super::prelude::*;
 
 #[derive(Default, Debug, Clone, PartialEq, Eq)]
 pub struct BlockBuilder {
     prebious_block: Option<Hash>,
     number: u64,
+    format: EncodingFormat,
 
     random_seed: u64,
     created_at: u64,
+    chain_id: Option<ChainId>,
 
     transactions: Vec<Transaction>,
-    minters: Vec<BlockMinter>
+    minters: Vec<BlockMinter>,
+    delegation: Option<DelegationCertificate>
 }
 
 impl BlockBuilder {
@@ -36,21 +41,30 @@ impl BlockBuilder {
         Self {
             prebious_block: None,
             number: 0,
+            format: EncodingFormat::LATEST,
 
             random_seed: safe_random_u64(),
-            created_at: timestamp(),
+            created_at: SystemClock.timestamp(),
+            chain_id: None,
 
             transactions: Vec::new(),
-            minters: Vec::new()
+            minters: Vec::new(),
+            delegation: None
         }
     }
 
     /// Create new block builder with a reference
     /// to the previous block and a proper number.
+    ///
+    /// Chain ID of the previous block is inherited.
     pub fn chained(previous: &Block) -> Self {
-        Self::new()
+        let mut builder = Self::new()
             .with_previous(previous.hash)
-            .with_number(previous.number + 1)
+            .with_number(previous.number + 1);
+
+        builder.chain_id = previous.chain_id;
+
+        builder
     }
 
     #[inline]
@@ -61,6 +75,46 @@ impl BlockBuilder {
         self
     }
 
+    #[inline]
+    /// Set block's creation time using the given clock.
+    ///
+    /// ```
+    /// use hyperborealib::prelude::*;
+    /// use hyperchain::prelude::*;
+    ///
+    /// let secret = SecretKey::random();
+    ///
+    /// let block = BlockBuilder::new()
+    ///     .with_clock(&MockClock::new(1000))
+    ///     .sign(&secret);
+    ///
+    /// assert_eq!(block.created_at(), 1000);
+    /// ```
+    pub fn with_clock(mut self, clock: &dyn Clock) -> Self {
+        self.created_at = clock.timestamp();
+
+        self
+    }
+
+    #[inline]
+    /// Change block's encoding format.
+    ///
+    /// Latest format is used by default.
+    pub fn with_format(mut self, format: EncodingFormat) -> Self {
+        self.format = format;
+
+        self
+    }
+
+    #[inline]
+    /// Bind block to the blockchain
+    /// with given identifier.
+    pub fn with_chain_id(mut self, chain_id: ChainId) -> Self {
+        self.chain_id = Some(chain_id);
+
+        self
+    }
+
     #[inline]
     /// Set block's number.
     pub fn with_number(mut self, number: impl Into<u64>) -> Self {
@@ -85,27 +139,65 @@ impl BlockBuilder {
         self
     }
 
+    #[inline]
+    /// Attach delegation certificate to the block.
+    ///
+    /// Block must be signed by the certificate's
+    /// delegate (session key).
+    pub fn with_delegation(mut self, certificate: DelegationCertificate) -> Self {
+        self.delegation = Some(certificate);
+
+        self
+    }
+
     /// Build block by signing stored content's hash.
     pub fn sign(self, validator: &SecretKey) -> Block {
+        let mut block = self.unsigned(validator.public_key());
+
+        block.sign = validator.create_signature(block.hash.as_bytes());
+
+        block
+    }
+
+    /// Build block by signing stored content's
+    /// hash using the given signer.
+    pub async fn sign_with<S: Signer>(self, signer: &S) -> Result<Block, S::Error> {
+        let mut block = self.unsigned(signer.public_key());
+
+        block.sign = signer.sign(&block.hash).await?;
+
+        Ok(block)
+    }
+
+    /// Build block with calculated hash which
+    /// can be signed on another machine.
+    pub fn prepare(self, validator: PublicKey) -> UnsignedBlock {
+        UnsignedBlock {
+            block: self.unsigned(validator)
+        }
+    }
+
+    /// Build block with calculated hash
+    /// but without signature.
+    fn unsigned(self, validator: PublicKey) -> Block {
         let mut block = Block {
             previous_block: self.prebious_block,
             hash: Hash::default(),
             number: self.number,
+            format: self.format,
 
             random_seed: self.random_seed,
             created_at: self.created_at,
+            chain_id: self.chain_id,
 
             transactions: self.transactions,
             minters: self.minters,
-            validator: validator.public_key(),
-            sign: vec![]
+            validator,
+            sign: vec![],
+            delegation: self.delegation
         };
 
-        let hash = block.calculate_hash();
-        let sign = validator.create_signature(hash.as_bytes());
-
-        block.hash = hash;
-        block.sign = sign;
+        block.hash = block.calculate_hash();
 
         block
     }
@@ -127,6 +219,8 @@ impl BlockBuilder {
 
 #[cfg(test)]
 pub(crate) mod tests {
+    use hyperborealib::exports::tokio;
+
     use crate::block::transaction::builder::tests::{
         get_message,
         get_announcement
@@ -170,4 +264,20 @@ pub(crate) mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn sign_with() -> Result<(), BlockValidationError> {
+        let secret = SecretKey::random();
+
+        let block = BlockBuilder::new()
+            .add_transaction(get_message().0)
+            .sign_with(&secret)
+            .await
+            .unwrap();
+
+        assert_eq!(block.validator, secret.public_key());
+        assert!(block.validate()?.is_valid());
+
+        Ok(())
+    }
 }
@@ -0,0 +1,66 @@
+use serde::{Serialize, Deserialize};
+
+use super::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, std::hash::Hash, Serialize, Deserialize)]
+/// Identifier of the blockchain.
+///
+/// Blocks and transactions made with a chain ID
+/// commit it into their hashes, so their signatures
+/// are not valid in any other blockchain.
+pub struct ChainId(Hash);
+
+impl ChainId {
+    #[inline]
+    /// Declare chain ID from the given hash.
+    pub fn new(hash: impl Into<Hash>) -> Self {
+        Self(hash.into())
+    }
+
+    #[inline]
+    /// Derive chain ID from the blockchain's genesis (root) block.
+    ///
+    /// Genesis block can't commit its own hash, so it
+    /// and its transactions must be made without chain ID.
+    /// They're accepted by the validation context with
+    /// this chain ID while other unbound blocks are not.
+    pub fn from_genesis(block: &Block) -> Self {
+        Self(block.get_hash())
+    }
+
+    #[inline]
+    /// Declare chain ID from the blockchain's name.
+    ///
+    /// ```
+    /// use hyperchain::prelude::*;
+    ///
+    /// assert_eq!(ChainId::from_name("mainnet"), ChainId::from_name("mainnet"));
+    /// assert_ne!(ChainId::from_name("mainnet"), ChainId::from_name("testnet"));
+    /// ```
+    pub fn from_name(name: impl AsRef<[u8]>) -> Self {
+        Self(Hash::hash_slice(name))
+    }
+
+    #[inline]
+    /// Get hash of the chain ID.
+    pub fn hash(&self) -> &Hash {
+        &self.0
+    }
+
+    #[inline]
+    pub fn to_base64(&self) -> String {
+        self.0.to_base64()
+    }
+
+    #[inline]
+    pub fn from_base64(chain_id: impl AsRef<str>) -> Result<Self, HashError> {
+        Hash::from_base64(chain_id).map(Self)
+    }
+}
+
+impl From<Hash> for ChainId {
+    #[inline]
+    fn from(value: Hash) -> Self {
+        Self(value)
+    }
+}
@@ -0,0 +1,222 @@
+use std::sync::Arc;
+
+use hyperborealib::crypto::asymmetric::PublicKey;
+use hyperborealib::crypto::Error as CryptographyError;
+
+use crate::clock::{Clock, SystemClock};
+
+use super::hash::Hash;
+use super::chain_id::ChainId;
+use super::limits::BlockLimits;
+use super::signature_cache::SignatureCache;
+use super::transaction::{TransactionBodyRules, TransactionTypeRegistry};
+
+#[derive(Debug, Clone)]
+/// Options used to validate blocks and transactions.
+pub struct ValidationContext {
+    /// Clock used to get the current time.
+    ///
+    /// Default is the system clock.
+    pub clock: Arc<dyn Clock>,
+
+    /// Maximal amount of seconds the creation time
+    /// of blocks and transactions can be ahead of
+    /// the current time.
+    ///
+    /// Default is 24 hours.
+    pub max_future_drift: u64,
+
+    /// Identifier of the validated blockchain.
+    ///
+    /// Blocks and transactions made for another
+    /// chain or without chain ID are rejected.
+    ///
+    /// Default is none.
+    pub chain_id: Option<ChainId>,
+
+    /// Accept blocks and transactions made without
+    /// chain ID when the context has one.
+    ///
+    /// Useful to load blockchains created before
+    /// chain IDs were introduced.
+    ///
+    /// Default is false.
+    pub allow_unbound: bool,
+
+    /// Semantic rules of transactions' bodies.
+    pub body_rules: TransactionBodyRules,
+
+    /// Registered custom transaction types.
+    ///
+    /// Default is empty registry which
+    /// accepts all the custom types.
+    pub transaction_types: TransactionTypeRegistry,
+
+    /// Limits of the blocks' content.
+    pub block_limits: BlockLimits,
+
+    /// Cache of verified signatures.
+    ///
+    /// Default is none.
+    pub signature_cache: Option<Arc<SignatureCache>>,
+
+    /// Require transactions to have nonces
+    /// increasing by one for each author.
+    ///
+    /// Default is false.
+    pub require_nonces: bool,
+
+    /// Require transactions' references to point
+    /// to the transactions included in the blockchain.
+    ///
+    /// Default is false.
+    pub require_references: bool
+}
+
+impl Default for ValidationContext {
+    fn default() -> Self {
+        Self {
+            clock: Arc::new(SystemClock),
+            max_future_drift: 24 * 60 * 60,
+            chain_id: None,
+            allow_unbound: false,
+            body_rules: TransactionBodyRules::default(),
+            transaction_types: TransactionTypeRegistry::default(),
+            block_limits: BlockLimits::default(),
+            signature_cache: None,
+            require_nonces: false,
+            require_references: false
+        }
+    }
+}
+
+impl ValidationContext {
+    #[inline]
+    /// Change validation clock.
+    ///
+    /// Shared clocks can be used to change
+    /// the time after the context is built.
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use hyperchain::prelude::*;
+    ///
+    /// let clock = Arc::new(MockClock::new(1000));
+    ///
+    /// let context = ValidationContext::default()
+    ///     .with_clock(clock.clone());
+    ///
+    /// clock.advance(20);
+    ///
+    /// assert_eq!(context.clock.timestamp(), 1020);
+    /// ```
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Arc::new(clock);
+
+        self
+    }
+
+    #[inline]
+    /// Change maximal allowed future drift.
+    pub fn with_max_future_drift(mut self, seconds: u64) -> Self {
+        self.max_future_drift = seconds;
+
+        self
+    }
+
+    #[inline]
+    /// Change expected chain ID.
+    pub fn with_chain_id(mut self, chain_id: ChainId) -> Self {
+        self.chain_id = Some(chain_id);
+
+        self
+    }
+
+    #[inline]
+    /// Change whether blocks and transactions made
+    /// without chain ID are accepted.
+    pub fn with_allowed_unbound(mut self, allowed: bool) -> Self {
+        self.allow_unbound = allowed;
+
+        self
+    }
+
+    #[inline]
+    /// Change transactions' bodies rules.
+    pub fn with_body_rules(mut self, rules: TransactionBodyRules) -> Self {
+        self.body_rules = rules;
+
+        self
+    }
+
+    #[inline]
+    /// Change custom transaction types registry.
+    pub fn with_transaction_types(mut self, registry: TransactionTypeRegistry) -> Self {
+        self.transaction_types = registry;
+
+        self
+    }
+
+    #[inline]
+    /// Change blocks' content limits.
+    pub fn with_block_limits(mut self, limits: BlockLimits) -> Self {
+        self.block_limits = limits;
+
+        self
+    }
+
+    #[inline]
+    /// Change verified signatures cache.
+    ///
+    /// The cache can be shared between
+    /// multiple contexts.
+    pub fn with_signature_cache(mut self, cache: Arc<SignatureCache>) -> Self {
+        self.signature_cache = Some(cache);
+
+        self
+    }
+
+    #[inline]
+    /// Change whether transactions' nonces are required.
+    pub fn with_required_nonces(mut self, required: bool) -> Self {
+        self.require_nonces = required;
+
+        self
+    }
+
+    #[inline]
+    /// Change whether transactions' references
+    /// must point to known transactions.
+    pub fn with_required_references(mut self, required: bool) -> Self {
+        self.require_references = required;
+
+        self
+    }
+
+    #[inline]
+    /// Check if given chain ID is accepted by the context.
+    pub fn is_chain_id_allowed(&self, chain_id: Option<ChainId>) -> bool {
+        match (self.chain_id, chain_id) {
+            (Some(expected), Some(chain_id)) => expected == chain_id,
+            (Some(_), None) => self.allow_unbound,
+            (None, _) => true
+        }
+    }
+
+    #[inline]
+    /// Get maximal allowed creation timestamp
+    /// of blocks and transactions.
+    pub fn max_timestamp(&self) -> u64 {
+        self.clock.timestamp().saturating_add(self.max_future_drift)
+    }
+
+    /// Verify signature of the given hash,
+    /// using signatures cache if available.
+    pub fn verify_signature(&self, hash: &Hash, signer: &PublicKey, sign: &[u8]) -> Result<bool, CryptographyError> {
+        match &self.signature_cache {
+            Some(cache) => cache.verify(hash, signer, sign),
+            None => signer.verify_signature(hash.as_bytes(), sign)
+        }
+    }
+}
@@ -0,0 +1,448 @@
+use serde::{Serialize, Deserialize};
+use serde_json::{json, Value as Json};
+
+use hyperborealib::crypto::asymmetric::{SecretKey, PublicKey};
+use hyperborealib::crypto::encoding::base64;
+use hyperborealib::crypto::Error as CryptographyError;
+
+use hyperborealib::rest_api::{
+    AsJson,
+    AsJsonError
+};
+
+use super::prelude::*;
+use super::format::FieldsHasher;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+/// Reason why a delegation certificate
+/// can't be used to sign a block.
+pub enum DelegationIssue {
+    /// Certificate is not signed by its authority.
+    InvalidSign,
+
+    /// Block is signed by another key.
+    DelegateMismatch,
+
+    /// Certificate is made for another blockchain
+    /// or is not bound to the validated one.
+    ChainMismatch,
+
+    /// Certificate has neither time
+    /// nor height upper bound.
+    Unbounded,
+
+    /// Block is created outside of the
+    /// certificate's time bounds.
+    OutOfTimeBounds,
+
+    /// Block's number is outside of the
+    /// certificate's height bounds.
+    OutOfHeightBounds
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+/// Certificate allowing a session key to sign
+/// blocks on behalf of an authority.
+///
+/// Certificate must be bounded by time
+/// (block's creation time) or height
+/// (block's number), or both.
+///
+/// ```
+/// use hyperborealib::prelude::*;
+/// use hyperchain::prelude::*;
+///
+/// let authority = SecretKey::random();
+/// let session = SecretKey::random();
+///
+/// let certificate = DelegationBuilder::new(session.public_key())
+///     .with_height_bounds(0, 1000)
+///     .sign(&authority);
+///
+/// let block = BlockBuilder::new()
+///     .with_delegation(certificate)
+///     .sign(&session);
+///
+/// assert_eq!(block.authority(), &authority.public_key());
+/// assert!(block.validate().unwrap().is_valid());
+/// ```
+pub struct DelegationCertificate {
+    pub(crate) authority: PublicKey,
+    pub(crate) delegate: PublicKey,
+    pub(crate) chain_id: Option<ChainId>,
+    pub(crate) not_before: Option<u64>,
+    pub(crate) not_after: Option<u64>,
+    pub(crate) min_number: Option<u64>,
+    pub(crate) max_number: Option<u64>,
+    pub(crate) sign: Vec<u8>
+}
+
+impl DelegationCertificate {
+    /// Prefix of the hashed data of certificates.
+    pub const HASH_DOMAIN: &'static [u8] = b"hyperchain/delegation";
+
+    #[inline]
+    /// Public key of the authority which
+    /// issued the certificate.
+    pub fn authority(&self) -> &PublicKey {
+        &self.authority
+    }
+
+    #[inline]
+    /// Public key of the session key.
+    pub fn delegate(&self) -> &PublicKey {
+        &self.delegate
+    }
+
+    #[inline]
+    /// Identifier of the blockchain
+    /// this certificate is made for.
+    pub fn chain_id(&self) -> Option<ChainId> {
+        self.chain_id
+    }
+
+    #[inline]
+    /// Minimal and maximal creation time
+    /// of the delegated blocks.
+    pub fn time_bounds(&self) -> (Option<u64>, Option<u64>) {
+        (self.not_before, self.not_after)
+    }
+
+    #[inline]
+    /// Minimal and maximal number
+    /// of the delegated blocks.
+    pub fn height_bounds(&self) -> (Option<u64>, Option<u64>) {
+        (self.min_number, self.max_number)
+    }
+
+    #[inline]
+    /// Authority's signature of the certificate's hash.
+    pub fn sign(&self) -> &[u8] {
+        &self.sign
+    }
+
+    /// Get total size of the certificate's fields in bytes.
+    pub fn fields_size(&self) -> usize {
+        let optional_fields = [self.not_before, self.not_after, self.min_number, self.max_number]
+            .into_iter()
+            .flatten()
+            .count();
+
+        let chain_id = if self.chain_id.is_some() { Hash::BYTES } else { 0 };
+
+        self.authority.to_bytes().len() +
+            self.delegate.to_bytes().len() +
+            chain_id + optional_fields * 8 +
+            self.sign.len()
+    }
+
+    /// Calculate hash of the certificate.
+    pub fn hash(&self) -> Hash {
+        fn optional(value: Option<u64>) -> Vec<u8> {
+            value.map(|value| value.to_be_bytes().to_vec())
+                .unwrap_or_default()
+        }
+
+        let mut hasher = FieldsHasher::new(Self::HASH_DOMAIN, EncodingFormat::V2);
+
+        match &self.chain_id {
+            Some(chain_id) => hasher.field(chain_id.hash().as_bytes()),
+            None => hasher.field(b"")
+        };
+
+        hasher.field(self.authority.to_bytes())
+            .field(self.delegate.to_bytes())
+            .field(optional(self.not_before))
+            .field(optional(self.not_after))
+            .field(optional(self.min_number))
+            .field(optional(self.max_number));
+
+        hasher.finalize()
+    }
+
+    /// Verify that the certificate allows
+    /// to sign the given block.
+    pub fn check(&self, block: &Block, context: &ValidationContext) -> Result<Option<DelegationIssue>, CryptographyError> {
+        if !context.verify_signature(&self.hash(), &self.authority, &self.sign)? {
+            return Ok(Some(DelegationIssue::InvalidSign));
+        }
+
+        if self.delegate != block.validator {
+            return Ok(Some(DelegationIssue::DelegateMismatch));
+        }
+
+        // Certificate must be bound to the validated blockchain,
+        // unless unbound data is explicitly allowed.
+        if !context.is_chain_id_allowed(self.chain_id) {
+            return Ok(Some(DelegationIssue::ChainMismatch));
+        }
+
+        if let (Some(chain_id), Some(block_chain_id)) = (self.chain_id, block.chain_id) {
+            if chain_id != block_chain_id {
+                return Ok(Some(DelegationIssue::ChainMismatch));
+            }
+        }
+
+        if self.not_after.is_none() && self.max_number.is_none() {
+            return Ok(Some(DelegationIssue::Unbounded));
+        }
+
+        let in_bounds = |value: u64, min: Option<u64>, max: Option<u64>| {
+            min.map(|min| value >= min).unwrap_or(true) &&
+            max.map(|max| value <= max).unwrap_or(true)
+        };
+
+        if !in_bounds(block.created_at, self.not_before, self.not_after) {
+            return Ok(Some(DelegationIssue::OutOfTimeBounds));
+        }
+
+        if !in_bounds(block.number, self.min_number, self.max_number) {
+            return Ok(Some(DelegationIssue::OutOfHeightBounds));
+        }
+
+        Ok(None)
+    }
+}
+
+impl AsJson for DelegationCertificate {
+    fn to_json(&self) -> Result<Json, AsJsonError> {
+        Ok(json!({
+            "format": 1,
+            "certificate": {
+                "authority": self.authority.to_base64(),
+                "delegate": self.delegate.to_base64(),
+                "chain_id": self.chain_id.map(|chain_id| chain_id.to_base64()),
+                "time": {
+                    "not_before": self.not_before,
+                    "not_after": self.not_after
+                },
+                "height": {
+                    "min": self.min_number,
+                    "max": self.max_number
+                },
+                "sign": base64::encode(&self.sign)
+            }
+        }))
+    }
+
+    fn from_json(json: &Json) -> Result<Self, AsJsonError> where Self: Sized {
+        let Some(format) = json.get("format").and_then(Json::as_u64) else {
+            return Err(AsJsonError::FieldNotFound("format"));
+        };
+
+        match format {
+            1 => {
+                let Some(certificate) = json.get("certificate") else {
+                    return Err(AsJsonError::FieldNotFound("certificate"));
+                };
+
+                let Some(time) = certificate.get("time") else {
+                    return Err(AsJsonError::FieldNotFound("certificate.time"));
+                };
+
+                let Some(height) = certificate.get("height") else {
+                    return Err(AsJsonError::FieldNotFound("certificate.height"));
+                };
+
+                let get_optional = |json: &Json, name: &str, field: &'static str| {
+                    match json.get(name) {
+                        None | Some(Json::Null) => Ok(None),
+
+                        Some(value) => value.as_u64()
+                            .map(Some)
+                            .ok_or(AsJsonError::FieldValueInvalid(field))
+                    }
+                };
+
+                Ok(Self {
+                    authority: certificate.get("authority")
+                        .and_then(Json::as_str)
+                        .map(PublicKey::from_base64)
+                        .ok_or_else(|| AsJsonError::FieldValueInvalid("certificate.authority"))??,
+
+                    delegate: certificate.get("delegate")
+                        .and_then(Json::as_str)
+                        .map(PublicKey::from_base64)
+                        .ok_or_else(|| AsJsonError::FieldValueInvalid("certificate.delegate"))??,
+
+                    chain_id: match certificate.get("chain_id") {
+                        None | Some(Json::Null) => None,
+
+                        Some(chain_id) => Some(chain_id.as_str()
+                            .map(ChainId::from_base64)
+                            .ok_or_else(|| AsJsonError::FieldValueInvalid("certificate.chain_id"))?
+                            .map_err(|err| AsJsonError::Other(err.into()))?)
+                    },
+
+                    not_before: get_optional(time, "not_before", "certificate.time.not_before")?,
+                    not_after: get_optional(time, "not_after", "certificate.time.not_after")?,
+                    min_number: get_optional(height, "min", "certificate.height.min")?,
+                    max_number: get_optional(height, "max", "certificate.height.max")?,
+
+                    sign: certificate.get("sign")
+                        .and_then(Json::as_str)
+                        .map(base64::decode)
+                        .ok_or_else(|| AsJsonError::FieldValueInvalid("certificate.sign"))??
+                })
+            }
+
+            version => Err(AsJsonError::InvalidStandard(version))
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Builder of the delegation certificates.
+pub struct DelegationBuilder {
+    delegate: PublicKey,
+    chain_id: Option<ChainId>,
+    not_before: Option<u64>,
+    not_after: Option<u64>,
+    min_number: Option<u64>,
+    max_number: Option<u64>
+}
+
+impl DelegationBuilder {
+    #[inline]
+    /// Delegate blocks signing to the given session key.
+    pub fn new(delegate: PublicKey) -> Self {
+        Self {
+            delegate,
+            chain_id: None,
+            not_before: None,
+            not_after: None,
+            min_number: None,
+            max_number: None
+        }
+    }
+
+    #[inline]
+    /// Bind certificate to the blockchain
+    /// with given identifier.
+    pub fn with_chain_id(mut self, chain_id: ChainId) -> Self {
+        self.chain_id = Some(chain_id);
+
+        self
+    }
+
+    #[inline]
+    /// Allow signing blocks created within
+    /// the given timestamps (inclusive).
+    pub fn with_time_bounds(mut self, not_before: u64, not_after: u64) -> Self {
+        self.not_before = Some(not_before);
+        self.not_after = Some(not_after);
+
+        self
+    }
+
+    #[inline]
+    /// Allow signing blocks with numbers
+    /// within the given range (inclusive).
+    pub fn with_height_bounds(mut self, min_number: u64, max_number: u64) -> Self {
+        self.min_number = Some(min_number);
+        self.max_number = Some(max_number);
+
+        self
+    }
+
+    /// Build certificate by signing it
+    /// with the authority's key.
+    pub fn sign(self, authority: &SecretKey) -> DelegationCertificate {
+        let mut certificate = DelegationCertificate {
+            authority: authority.public_key(),
+            delegate: self.delegate,
+            chain_id: self.chain_id,
+            not_before: self.not_before,
+            not_after: self.not_after,
+            min_number: self.min_number,
+            max_number: self.max_number,
+            sign: vec![]
+        };
+
+        certificate.sign = authority.create_signature(certificate.hash().as_bytes());
+
+        certificate
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check() -> Result<(), CryptographyError> {
+        let authority = SecretKey::random();
+        let session = SecretKey::random();
+
+        let context = ValidationContext::default();
+
+        let certificate = DelegationBuilder::new(session.public_key())
+            .with_height_bounds(1, 2)
+            .sign(&authority);
+
+        let certificate = DelegationCertificate::from_json(&certificate.to_json().unwrap()).unwrap();
+
+        let block = BlockBuilder::new()
+            .with_number(1_u64)
+            .sign(&session);
+
+        assert_eq!(certificate.check(&block, &context)?, None);
+
+        // Out of bounds
+        let block = BlockBuilder::new()
+            .with_number(3_u64)
+            .sign(&session);
+
+        assert_eq!(certificate.check(&block, &context)?, Some(DelegationIssue::OutOfHeightBounds));
+
+        // Signed by another key
+        let block = BlockBuilder::new()
+            .with_number(1_u64)
+            .sign(&authority);
+
+        assert_eq!(certificate.check(&block, &context)?, Some(DelegationIssue::DelegateMismatch));
+
+        // Chain ID
+        let mainnet = ChainId::from_name("mainnet");
+        let testnet = ChainId::from_name("testnet");
+
+        let block = BlockBuilder::new()
+            .with_number(1_u64)
+            .sign(&session);
+
+        let mainnet_context = ValidationContext::default()
+            .with_chain_id(mainnet);
+
+        assert_eq!(certificate.check(&block, &mainnet_context)?, Some(DelegationIssue::ChainMismatch));
+        assert_eq!(certificate.check(&block, &mainnet_context.clone().with_allowed_unbound(true))?, None);
+
+        let bound_certificate = DelegationBuilder::new(session.public_key())
+            .with_chain_id(mainnet)
+            .with_height_bounds(1, 2)
+            .sign(&authority);
+
+        assert_eq!(bound_certificate.check(&block, &mainnet_context)?, None);
+        assert_eq!(bound_certificate.check(&block, &ValidationContext::default().with_chain_id(testnet))?, Some(DelegationIssue::ChainMismatch));
+
+        // Unbounded certificate
+        let certificate = DelegationBuilder::new(session.public_key())
+            .sign(&authority);
+
+        let block = BlockBuilder::new()
+            .with_number(1_u64)
+            .sign(&session);
+
+        assert_eq!(certificate.check(&block, &context)?, Some(DelegationIssue::Unbounded));
+
+        // Forged certificate
+        let mut certificate = DelegationBuilder::new(session.public_key())
+            .with_height_bounds(1, 2)
+            .sign(&session);
+
+        certificate.authority = authority.public_key();
+
+        assert_eq!(certificate.check(&block, &context)?, Some(DelegationIssue::InvalidSign));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,92 @@
+use serde::{Serialize, Deserialize};
+
+use super::hash::Hash;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, std::hash::Hash, Serialize, Deserialize)]
+/// Encoding format of blocks and transactions.
+///
+/// Format defines the fields committed
+/// by the block's or transaction's hash.
+pub enum EncodingFormat {
+    /// Legacy format.
+    ///
+    /// Transaction's hash doesn't commit
+    /// to its creation time, and fields are
+    /// concatenated without separators.
+    V1,
+
+    /// Hash commits to every field and the format
+    /// itself, each field is prefixed by its length.
+    V2
+}
+
+impl EncodingFormat {
+    /// Format used for new blocks and transactions.
+    pub const LATEST: Self = Self::V2;
+
+    #[inline]
+    /// Legacy format, used for values serialized
+    /// before the format was stored.
+    pub fn legacy() -> Self {
+        Self::V1
+    }
+
+    #[inline]
+    /// Get numeric version of the format.
+    pub fn version(&self) -> u64 {
+        match self {
+            Self::V1 => 1,
+            Self::V2 => 2
+        }
+    }
+
+    #[inline]
+    /// Get format from its numeric version.
+    pub fn from_version(version: u64) -> Option<Self> {
+        match version {
+            1 => Some(Self::V1),
+            2 => Some(Self::V2),
+
+            _ => None
+        }
+    }
+}
+
+impl Default for EncodingFormat {
+    #[inline]
+    fn default() -> Self {
+        Self::LATEST
+    }
+}
+
+/// Hasher which prefixes each field with its length
+/// to prevent ambiguous concatenation.
+pub(crate) struct FieldsHasher(blake3::Hasher);
+
+impl FieldsHasher {
+    /// Create new hasher committing to the
+    /// given domain and encoding format.
+    pub fn new(domain: &[u8], format: EncodingFormat) -> Self {
+        let mut hasher = Self(blake3::Hasher::new());
+
+        hasher.field(domain);
+        hasher.field(format.version().to_be_bytes());
+
+        hasher
+    }
+
+    /// Update hash with the given field.
+    pub fn field(&mut self, bytes: impl AsRef<[u8]>) -> &mut Self {
+        let bytes = bytes.as_ref();
+
+        self.0.update(&(bytes.len() as u64).to_be_bytes());
+        self.0.update(bytes);
+
+        self
+    }
+
+    #[inline]
+    pub fn finalize(&self) -> Hash {
+        self.0.finalize().into()
+    }
+}
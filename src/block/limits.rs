@@ -0,0 +1,58 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// Limits of the block's content.
+pub struct BlockLimits {
+    /// Maximal amount of transactions in a block.
+    ///
+    /// Default is 4096.
+    pub max_transactions: usize,
+
+    /// Maximal amount of minters in a block.
+    ///
+    /// Default is 256.
+    pub max_minters: usize,
+
+    /// Maximal total size of the block's fields
+    /// in bytes, see `Block::fields_size`.
+    ///
+    /// This doesn't limit the serialized block's
+    /// length which is larger due to its encoding.
+    ///
+    /// Default is 16 MiB.
+    pub max_fields_size: usize
+}
+
+impl Default for BlockLimits {
+    fn default() -> Self {
+        Self {
+            max_transactions: 4096,
+            max_minters: 256,
+            max_fields_size: 16 * 1024 * 1024
+        }
+    }
+}
+
+impl BlockLimits {
+    #[inline]
+    /// Change maximal amount of transactions in a block.
+    pub fn with_max_transactions(mut self, max_transactions: usize) -> Self {
+        self.max_transactions = max_transactions;
+
+        self
+    }
+
+    #[inline]
+    /// Change maximal amount of minters in a block.
+    pub fn with_max_minters(mut self, max_minters: usize) -> Self {
+        self.max_minters = max_minters;
+
+        self
+    }
+
+    #[inline]
+    /// Change maximal total size of the block's fields.
+    pub fn with_max_fields_size(mut self, max_fields_size: usize) -> Self {
+        self.max_fields_size = max_fields_size;
+
+        self
+    }
+}
@@ -1,4 +1,6 @@
 use std::cmp::Ordering;
+use std::ops::ControlFlow;
+use std::collections::HashSet;
 
 use serde::{Serialize, Deserialize};
 use serde_json::{json, Value as Json};
@@ -7,17 +9,23 @@ use hyperborealib::crypto::asymmetric::PublicKey;
 use hyperborealib::crypto::encoding::base64;
 use hyperborealib::crypto::Error as CryptographyError;
 
-use hyperborealib::time::timestamp;
-
 use hyperborealib::rest_api::{
     AsJson,
     AsJsonError
 };
 
 pub mod hash;
+pub mod format;
+pub mod chain_id;
 pub mod transaction;
 pub mod minter;
+pub mod delegation;
 pub mod builder;
+pub mod offline;
+pub mod limits;
+pub mod signature_cache;
+pub mod context;
+pub mod report;
 
 pub mod prelude {
     pub use super::{
@@ -27,14 +35,24 @@ pub mod prelude {
     };
 
     pub use super::hash::*;
+    pub use super::format::EncodingFormat;
+    pub use super::chain_id::*;
     pub use super::minter::*;
+    pub use super::delegation::*;
     pub use super::builder::*;
+    pub use super::offline::*;
+    pub use super::limits::*;
+    pub use super::signature_cache::*;
+    pub use super::context::*;
+    pub use super::report::*;
 
     pub use super::transaction::prelude::*;
 }
 
 use prelude::*;
 
+use format::FieldsHasher;
+
 #[derive(Debug, thiserror::Error)]
 pub enum BlockValidationError {
     #[error("Failed to verify signature: {0}")]
@@ -44,7 +62,7 @@ pub enum BlockValidationError {
     TransactionValidationError(#[from] TransactionValidationError)
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum BlockValidationResult {
     /// Invalid creation timestamp.
     InvalidCreationTime {
@@ -69,6 +87,73 @@ pub enum BlockValidationResult {
         error: TransactionValidationResult,
     },
 
+    /// Block is made for another blockchain.
+    InvalidChainId {
+        expected: Option<ChainId>,
+        got: Option<ChainId>
+    },
+
+    /// Block has too many transactions.
+    TooManyTransactions {
+        count: usize,
+        max_transactions: usize
+    },
+
+    /// Block has too many minters.
+    TooManyMinters {
+        count: usize,
+        max_minters: usize
+    },
+
+    /// Block's fields are too large.
+    TooLarge {
+        fields_size: usize,
+        max_fields_size: usize
+    },
+
+    /// Transaction is stored in the block more than once.
+    DuplicateTransaction {
+        transaction: Hash
+    },
+
+    /// Minter is stored in the block more than once.
+    DuplicateMinter {
+        minter: PublicKey
+    },
+
+    /// Transaction is created later than the block.
+    InvalidTransactionCreationTime {
+        transaction: Hash,
+        created_at: u64,
+        block_created_at: u64
+    },
+
+    /// Block's number doesn't follow its parent's.
+    InvalidParentNumber {
+        number: u64,
+        parent_number: u64
+    },
+
+    /// Block doesn't reference its parent.
+    InvalidParentReference {
+        expected: Hash,
+        got: Option<Hash>
+    },
+
+    /// Block is created earlier than its parent.
+    InvalidParentCreationTime {
+        created_at: u64,
+        parent_created_at: u64
+    },
+
+    /// Block is signed by a session key
+    /// with invalid delegation certificate.
+    InvalidDelegation {
+        authority: PublicKey,
+        delegate: PublicKey,
+        issue: DelegationIssue
+    },
+
     Valid
 }
 
@@ -86,18 +171,29 @@ pub struct Block {
     pub(crate) hash: Hash,
     pub(crate) number: u64,
 
+    #[serde(default = "EncodingFormat::legacy")]
+    pub(crate) format: EncodingFormat,
+
     // Metadata
     pub(crate) random_seed: u64,
     pub(crate) created_at: u64,
+    pub(crate) chain_id: Option<ChainId>,
 
     // Body
     pub(crate) transactions: Vec<Transaction>,
     pub(crate) minters: Vec<BlockMinter>,
     pub(crate) validator: PublicKey,
-    pub(crate) sign: Vec<u8>
+    pub(crate) sign: Vec<u8>,
+
+    #[serde(default)]
+    pub(crate) delegation: Option<DelegationCertificate>
 }
 
 impl Block {
+    /// Prefix of the hashed data of
+    /// blocks with chain ID.
+    pub const HASH_DOMAIN: &'static [u8] = b"hyperchain/block";
+
     #[inline]
     /// Hash of the previous block.
     pub fn previous_block(&self) -> Option<Hash> {
@@ -110,6 +206,12 @@ impl Block {
         self.number
     }
 
+    #[inline]
+    /// Encoding format of the block.
+    pub fn format(&self) -> EncodingFormat {
+        self.format
+    }
+
     #[inline]
     /// UTC timestamp (amount of seconds) when
     /// this block was made.
@@ -117,6 +219,13 @@ impl Block {
         self.created_at
     }
 
+    #[inline]
+    /// Identifier of the blockchain
+    /// this block is made for.
+    pub fn chain_id(&self) -> Option<ChainId> {
+        self.chain_id
+    }
+
     #[inline]
     /// List of transactions signed in this block.
     pub fn transactions(&self) -> &[Transaction] {
@@ -135,6 +244,53 @@ impl Block {
         &self.validator
     }
 
+    #[inline]
+    /// Certificate allowing the block's validator
+    /// to sign blocks on behalf of an authority.
+    pub fn delegation(&self) -> Option<&DelegationCertificate> {
+        self.delegation.as_ref()
+    }
+
+    /// Get total size of the block's fields in bytes.
+    ///
+    /// This is not the serialized block's length: fields
+    /// are counted in their raw binary form while the
+    /// serialized block has encoding and format overhead.
+    pub fn fields_size(&self) -> usize {
+        let previous_block = if self.previous_block.is_some() { Hash::BYTES } else { 0 };
+        let chain_id = if self.chain_id.is_some() { Hash::BYTES } else { 0 };
+
+        let transactions = self.transactions.iter()
+            .map(Transaction::fields_size)
+            .sum::<usize>();
+
+        let minters = self.minters.iter()
+            .map(|minter| minter.public_key.to_bytes().len() + Hash::BYTES)
+            .sum::<usize>();
+
+        let delegation = self.delegation.as_ref()
+            .map(DelegationCertificate::fields_size)
+            .unwrap_or_default();
+
+        // Hash, number, random seed and creation time
+        Hash::BYTES + 24 + previous_block + chain_id +
+            transactions + minters + delegation +
+            self.validator.to_bytes().len() +
+            self.sign.len()
+    }
+
+    #[inline]
+    /// Public key of the authority responsible
+    /// for the block.
+    ///
+    /// This is either the delegating authority if the
+    /// block has a delegation certificate, or the validator.
+    pub fn authority(&self) -> &PublicKey {
+        self.delegation.as_ref()
+            .map(DelegationCertificate::authority)
+            .unwrap_or(&self.validator)
+    }
+
     #[inline]
     /// Digital signature of the block's hash.
     pub fn sign(&self) -> &[u8] {
@@ -160,9 +316,28 @@ impl Block {
     ///
     /// This is a relatively heavy function and
     /// it should not be called often.
+    ///
+    /// Delegation certificate is hashed only if it's
+    /// present, so hashes of blocks signed by their
+    /// authorities directly didn't change when
+    /// delegation was introduced.
     pub fn calculate_hash(&self) -> Hash {
+        match self.format {
+            EncodingFormat::V1 => self.calculate_hash_v1(),
+            EncodingFormat::V2 => self.calculate_hash_v2()
+        }
+    }
+
+    fn calculate_hash_v1(&self) -> Hash {
         let mut hasher = blake3::Hasher::new();
 
+        // Domain separation for blocks
+        // bound to a blockchain
+        if let Some(chain_id) = &self.chain_id {
+            hasher.update(Self::HASH_DOMAIN);
+            hasher.update(&chain_id.hash().as_bytes());
+        }
+
         // Header
         if let Some(hash) = &self.previous_block {
             hasher.update(&hash.as_bytes());
@@ -183,70 +358,383 @@ impl Block {
             hasher.update(&minter.hash().as_bytes());
         }
 
+        if let Some(delegation) = &self.delegation {
+            hasher.update(&delegation.hash().as_bytes());
+        }
+
         hasher.finalize().into()
     }
 
+    fn calculate_hash_v2(&self) -> Hash {
+        let mut hasher = FieldsHasher::new(Self::HASH_DOMAIN, EncodingFormat::V2);
+
+        // Empty fields for blocks without chain ID
+        // or previous block
+        match &self.chain_id {
+            Some(chain_id) => hasher.field(chain_id.hash().as_bytes()),
+            None => hasher.field(b"")
+        };
+
+        // Header
+        match &self.previous_block {
+            Some(hash) => hasher.field(hash.as_bytes()),
+            None => hasher.field(b"")
+        };
+
+        hasher.field(self.number.to_be_bytes());
+
+        // Metadata
+        hasher.field(self.random_seed.to_be_bytes())
+            .field(self.created_at.to_be_bytes());
+
+        // Body
+        hasher.field((self.transactions.len() as u64).to_be_bytes());
+
+        for transaction in &self.transactions {
+            hasher.field(transaction.calculate_hash().as_bytes());
+        }
+
+        hasher.field((self.minters.len() as u64).to_be_bytes());
+
+        for minter in &self.minters {
+            hasher.field(minter.hash().as_bytes());
+        }
+
+        hasher.field(self.validator.to_bytes());
+
+        if let Some(delegation) = &self.delegation {
+            hasher.field(delegation.hash().as_bytes());
+        }
+
+        hasher.finalize()
+    }
+
     /// Validate block.
     ///
     /// This method will:
     ///
     /// 1. Verify that the block's creation time
-    ///    is not higher than the current UTC time.
+    ///    is not higher than the current UTC time
+    ///    (+24h to allow clocks drift).
+    ///
+    /// 2. Verify that the block is made for
+    ///    the expected blockchain.
+    ///
+    /// 3. Verify block's content limits, that transactions
+    ///    and minters are not duplicated, and that transactions
+    ///    are not created later than the block.
     ///
-    /// 2. Calculate block hash and compare it
+    /// 4. Calculate block hash and compare it
     ///    with stored value.
     ///
-    /// 3. Verify block's signature.
+    /// 5. Verify block's signature and delegation
+    ///    certificate if the block has one.
     ///
-    /// 4. Verify each stored transaction.
+    /// 6. Verify each stored transaction. Transactions
+    ///    must be made for the block's blockchain and
+    ///    must not be expired at the block's creation time.
     ///
     /// This is not recommended to call this method often.
+    #[inline]
     pub fn validate(&self) -> Result<BlockValidationResult, BlockValidationError> {
-        // Validate block's creation time (+24h just in case)
-        if self.created_at > timestamp() + 24 * 60 * 60 {
-            return Ok(BlockValidationResult::InvalidCreationTime {
+        self.validate_with(&ValidationContext::default())
+    }
+
+    /// Validate block using given validation context.
+    ///
+    /// Same as `validate` but uses the context's
+    /// clock and future drift tolerance to verify
+    /// the block's creation time, and the context's
+    /// chain ID.
+    pub fn validate_with(&self, context: &ValidationContext) -> Result<BlockValidationResult, BlockValidationError> {
+        let mut result = Ok(BlockValidationResult::Valid);
+
+        let _ = self.check_rules(context, &mut |issue| {
+            result = match issue {
+                BlockIssue::Block(result) => Ok(result),
+                BlockIssue::BlockError(err) => Err(err),
+
+                BlockIssue::Transaction(transaction, error) => Ok(BlockValidationResult::InvalidTransaction {
+                    transaction: Box::new(transaction.clone()),
+                    error
+                }),
+
+                BlockIssue::TransactionError(_, err) => Err(err.into())
+            };
+
+            ControlFlow::Break(())
+        });
+
+        result
+    }
+
+    #[inline]
+    /// Verify that the block follows the given parent block.
+    ///
+    /// This method will check the block's number continuity,
+    /// reference to the parent's hash and that the block
+    /// is not created earlier than its parent.
+    pub fn check_parent(&self, parent: &Block) -> Option<BlockValidationResult> {
+        BlockLink::from(parent).check_all(self)
+            .into_iter()
+            .next()
+    }
+
+    #[inline]
+    /// Validate block and verify that it
+    /// follows the given parent block.
+    ///
+    /// Unlike `validate` this method can
+    /// verify the block's linkage to the chain.
+    pub fn validate_with_parent(&self, parent: &Block) -> Result<BlockValidationResult, BlockValidationError> {
+        self.validate_with_parent_and_context(parent, &ValidationContext::default())
+    }
+
+    /// Validate block using given validation context
+    /// and verify that it follows the given parent block.
+    pub fn validate_with_parent_and_context(&self, parent: &Block, context: &ValidationContext) -> Result<BlockValidationResult, BlockValidationError> {
+        if let Some(result) = self.check_parent(parent) {
+            return Ok(result);
+        }
+
+        self.validate_with(context)
+    }
+
+    /// Validate block, collecting all the found
+    /// issues in a new report.
+    ///
+    /// Unlike `validate` this method will not stop
+    /// on the first found problem.
+    #[inline]
+    pub fn validate_report(&self) -> ValidationReport {
+        self.validate_report_with(&ValidationContext::default())
+    }
+
+    /// Validate block using given validation context,
+    /// collecting all the found issues in a new report.
+    pub fn validate_report_with(&self, context: &ValidationContext) -> ValidationReport {
+        let mut report = ValidationReport::new();
+
+        self.report_issues(context, &mut report);
+
+        report
+    }
+
+    /// Store all the block's issues in the given report.
+    pub(crate) fn report_issues(&self, context: &ValidationContext, report: &mut ValidationReport) {
+        let block_number = Some(self.number);
+
+        let _ = self.check_rules(context, &mut |issue| {
+            let (transaction, issue) = match issue {
+                BlockIssue::Block(result) => (None, ValidationIssue::Block(result)),
+                BlockIssue::BlockError(err) => (None, ValidationIssue::BlockError(err.into())),
+                BlockIssue::Transaction(transaction, result) => (Some(transaction.hash), ValidationIssue::Transaction(result)),
+                BlockIssue::TransactionError(transaction, err) => (Some(transaction.hash), ValidationIssue::TransactionError(err.into()))
+            };
+
+            report.push_error(block_number, transaction, issue);
+
+            ControlFlow::Continue(())
+        });
+
+        report.count_block();
+        report.count_transactions(self.transactions.len() as u64);
+    }
+
+    /// Verify all the block's rules, passing violated
+    /// ones to the callback until it asks to stop.
+    ///
+    /// Both `validate_with` and `report_issues` are built
+    /// on top of this method so they can't diverge.
+    pub(crate) fn check_rules<'a>(
+        &'a self,
+        context: &ValidationContext,
+        issue: &mut impl FnMut(BlockIssue<'a>) -> ControlFlow<()>
+    ) -> ControlFlow<()> {
+        // Validate block's creation time
+        if self.created_at > context.max_timestamp() {
+            issue(BlockIssue::Block(BlockValidationResult::InvalidCreationTime {
                 created_at: self.created_at
-            });
+            }))?;
+        }
+
+        // Validate block's chain ID
+        if !context.is_chain_id_allowed(self.chain_id) && !self.is_chain_genesis(context) {
+            issue(BlockIssue::Block(BlockValidationResult::InvalidChainId {
+                expected: context.chain_id,
+                got: self.chain_id
+            }))?;
+        }
+
+        // Validate block's content
+        for result in self.check_content(&context.block_limits) {
+            issue(BlockIssue::Block(result))?;
         }
 
         // Validate block's hash
         let hash = self.calculate_hash();
 
         if self.hash != hash {
-            return Ok(BlockValidationResult::InvalidHash {
+            issue(BlockIssue::Block(BlockValidationResult::InvalidHash {
                 stored: self.hash,
                 calculated: hash
-            });
+            }))?;
         }
 
         // Validate block hash's signature
-        if !self.validator.verify_signature(self.hash.as_bytes(), &self.sign)? {
-            return Ok(BlockValidationResult::InvalidSign {
+        match context.verify_signature(&self.hash, &self.validator, &self.sign) {
+            Ok(true) => (),
+
+            Ok(false) => issue(BlockIssue::Block(BlockValidationResult::InvalidSign {
                 hash: self.hash,
                 sign: self.sign.clone()
-            });
+            }))?,
+
+            Err(err) => issue(BlockIssue::BlockError(err.into()))?
+        }
+
+        // Validate block's delegation certificate
+        match self.check_delegation(context) {
+            Ok(Some(result)) => issue(BlockIssue::Block(result))?,
+            Ok(None) => (),
+
+            Err(err) => issue(BlockIssue::BlockError(err.into()))?
         }
 
         // Validate block's stored transactions
+        let context = self.transactions_context(context);
+
         for transaction in &self.transactions {
-            let result = transaction.validate()?;
+            transaction.check_rules(&context, self.created_at, &mut |result| {
+                issue(match result {
+                    Ok(result) => BlockIssue::Transaction(transaction, result),
+                    Err(err) => BlockIssue::TransactionError(transaction, err)
+                })
+            })?;
+        }
 
-            if !result.is_valid() {
-                return Ok(BlockValidationResult::InvalidTransaction {
-                    transaction: Box::new(transaction.clone()),
-                    error: result
+        ControlFlow::Continue(())
+    }
+
+    /// Verify block's content limits and consistency,
+    /// returning all the violated rules.
+    fn check_content(&self, limits: &BlockLimits) -> Vec<BlockValidationResult> {
+        let mut results = Vec::new();
+
+        // Validate block's limits
+        if self.transactions.len() > limits.max_transactions {
+            results.push(BlockValidationResult::TooManyTransactions {
+                count: self.transactions.len(),
+                max_transactions: limits.max_transactions
+            });
+        }
+
+        if self.minters.len() > limits.max_minters {
+            results.push(BlockValidationResult::TooManyMinters {
+                count: self.minters.len(),
+                max_minters: limits.max_minters
+            });
+        }
+
+        let fields_size = self.fields_size();
+
+        if fields_size > limits.max_fields_size {
+            results.push(BlockValidationResult::TooLarge {
+                fields_size,
+                max_fields_size: limits.max_fields_size
+            });
+        }
+
+        // Validate block's transactions
+        let mut transactions = HashSet::with_capacity(self.transactions.len());
+
+        for transaction in &self.transactions {
+            if !transactions.insert(transaction.hash) {
+                results.push(BlockValidationResult::DuplicateTransaction {
+                    transaction: transaction.hash
+                });
+            }
+
+            if transaction.created_at > self.created_at {
+                results.push(BlockValidationResult::InvalidTransactionCreationTime {
+                    transaction: transaction.hash,
+                    created_at: transaction.created_at,
+                    block_created_at: self.created_at
                 });
             }
         }
 
-        Ok(BlockValidationResult::Valid)
+        // Validate block's minters
+        let mut minters = HashSet::with_capacity(self.minters.len());
+
+        for minter in &self.minters {
+            if !minters.insert(&minter.public_key) {
+                results.push(BlockValidationResult::DuplicateMinter {
+                    minter: minter.public_key.clone()
+                });
+            }
+        }
+
+        results
+    }
+
+    /// Verify block's delegation certificate if it has one.
+    pub(crate) fn check_delegation(&self, context: &ValidationContext) -> Result<Option<BlockValidationResult>, CryptographyError> {
+        let Some(delegation) = &self.delegation else {
+            return Ok(None);
+        };
+
+        // Genesis block's certificate can't be bound
+        // to the chain derived from this block.
+        let genesis_context;
+
+        let context = if self.is_chain_genesis(context) {
+            genesis_context = context.clone().with_allowed_unbound(true);
+
+            &genesis_context
+        } else {
+            context
+        };
+
+        Ok(delegation.check(self, context)?.map(|issue| {
+            BlockValidationResult::InvalidDelegation {
+                authority: delegation.authority.clone(),
+                delegate: delegation.delegate.clone(),
+                issue
+            }
+        }))
+    }
+
+    /// Check if the block is the unbound root block
+    /// which hash is used as the expected chain ID.
+    ///
+    /// Such block can't commit its own hash, so it, its
+    /// delegation certificate and its transactions are
+    /// accepted without chain ID.
+    pub(crate) fn is_chain_genesis(&self, context: &ValidationContext) -> bool {
+        self.number == 0
+            && self.previous_block.is_none()
+            && self.chain_id.is_none()
+            && context.chain_id == Some(ChainId::from_genesis(self))
+    }
+
+    /// Get context to validate block's transactions.
+    ///
+    /// Transactions must be made for the
+    /// block's blockchain if it's specified.
+    fn transactions_context(&self, context: &ValidationContext) -> ValidationContext {
+        match (context.chain_id, self.chain_id) {
+            (None, Some(chain_id)) => context.clone().with_chain_id(chain_id),
+            (Some(_), None) if self.is_chain_genesis(context) => context.clone().with_allowed_unbound(true),
+            _ => context.clone()
+        }
     }
 }
 
 impl AsJson for Block {
     fn to_json(&self) -> Result<Json, AsJsonError> {
-        Ok(json!({
-            "format": 1,
+        let mut json = json!({
+            "format": self.format.version(),
             "block": {
                 "previous": self.previous_block.map(|hash| hash.to_base64()),
                 "current": self.hash.to_base64(),
@@ -268,7 +756,18 @@ impl AsJson for Block {
                     "sign": base64::encode(&self.sign)
                 }
             }
-        }))
+        });
+
+        // Keep JSON of blocks without chain ID unchanged
+        if let Some(chain_id) = &self.chain_id {
+            json["block"]["metadata"]["chain_id"] = Json::String(chain_id.to_base64());
+        }
+
+        if let Some(delegation) = &self.delegation {
+            json["block"]["content"]["delegation"] = delegation.to_json()?;
+        }
+
+        Ok(json)
     }
 
     fn from_json(json: &Json) -> Result<Self, AsJsonError> where Self: Sized {
@@ -277,7 +776,7 @@ impl AsJson for Block {
         };
 
         match format {
-            1 => {
+            1 | 2 => {
                 let Some(block) = json.get("block") else {
                     return Err(AsJsonError::FieldNotFound("block"));
                 };
@@ -318,6 +817,9 @@ impl AsJson for Block {
                         .and_then(Json::as_u64)
                         .ok_or_else(|| AsJsonError::FieldValueInvalid("block.number"))?,
 
+                    format: EncodingFormat::from_version(format)
+                        .ok_or(AsJsonError::InvalidStandard(format))?,
+
                     random_seed: metadata.get("random_seed")
                         .and_then(Json::as_u64)
                         .ok_or_else(|| AsJsonError::FieldValueInvalid("block.metadata.random_seed"))?,
@@ -326,6 +828,15 @@ impl AsJson for Block {
                         .and_then(Json::as_u64)
                         .ok_or_else(|| AsJsonError::FieldValueInvalid("block.metadata.created_at"))?,
 
+                    chain_id: match metadata.get("chain_id") {
+                        None | Some(Json::Null) => None,
+
+                        Some(chain_id) => Some(chain_id.as_str()
+                            .map(ChainId::from_base64)
+                            .ok_or_else(|| AsJsonError::FieldValueInvalid("block.metadata.chain_id"))?
+                            .map_err(|err| AsJsonError::Other(err.into()))?)
+                    },
+
                     transactions: content.get("transactions")
                         .and_then(Json::as_array)
                         .map(|transactions| {
@@ -352,7 +863,13 @@ impl AsJson for Block {
                     sign: content.get("sign")
                         .and_then(Json::as_str)
                         .map(base64::decode)
-                        .ok_or_else(|| AsJsonError::FieldValueInvalid("block.content.sign"))??
+                        .ok_or_else(|| AsJsonError::FieldValueInvalid("block.content.sign"))??,
+
+                    delegation: match content.get("delegation") {
+                        None | Some(Json::Null) => None,
+
+                        Some(delegation) => Some(DelegationCertificate::from_json(delegation)?)
+                    }
                 })
             }
 
@@ -361,6 +878,65 @@ impl AsJson for Block {
     }
 }
 
+/// Violated block's rule found by `Block::check_rules`.
+pub(crate) enum BlockIssue<'a> {
+    Block(BlockValidationResult),
+    BlockError(BlockValidationError),
+    Transaction(&'a Transaction, TransactionValidationResult),
+    TransactionError(&'a Transaction, TransactionValidationError)
+}
+
+/// Fields of the block which its child
+/// block must follow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct BlockLink {
+    pub number: u64,
+    pub hash: Hash,
+    pub created_at: u64
+}
+
+impl From<&Block> for BlockLink {
+    #[inline]
+    fn from(block: &Block) -> Self {
+        Self {
+            number: block.number,
+            hash: block.hash,
+            created_at: block.created_at
+        }
+    }
+}
+
+impl BlockLink {
+    /// Verify that the given block follows this one,
+    /// returning all the violated rules.
+    pub fn check_all(&self, block: &Block) -> Vec<BlockValidationResult> {
+        let mut results = Vec::new();
+
+        if self.number.checked_add(1) != Some(block.number) {
+            results.push(BlockValidationResult::InvalidParentNumber {
+                number: block.number,
+                parent_number: self.number
+            });
+        }
+
+        if block.previous_block != Some(self.hash) {
+            results.push(BlockValidationResult::InvalidParentReference {
+                expected: self.hash,
+                got: block.previous_block
+            });
+        }
+
+        if block.created_at < self.created_at {
+            results.push(BlockValidationResult::InvalidParentCreationTime {
+                created_at: block.created_at,
+                parent_created_at: self.created_at
+            });
+        }
+
+        results
+    }
+}
+
 impl PartialOrd<Block> for Block {
     #[inline]
     fn partial_cmp(&self, other: &Block) -> Option<Ordering> {
@@ -390,6 +966,150 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn validate_report() -> Result<(), serde_json::Error> {
+        let mut block = get_chained().1;
+
+        assert!(block.validate_report().is_valid());
+        assert!(block.validate_report().findings().is_empty());
+
+        // Break block's creation time and transaction's sign
+        block.created_at = u64::MAX;
+        block.transactions[0].sign = vec![];
+
+        let report = block.validate_report();
+
+        assert!(!report.is_valid());
+
+        assert_eq!(report.validated_blocks(), 1);
+        assert_eq!(report.validated_transactions(), 2);
+
+        // Creation time, hash and transaction's sign
+        assert_eq!(report.findings().len(), 3);
+
+        assert!(report.findings().iter().all(|finding| finding.block_number == Some(block.number)));
+
+        assert_eq!(report.findings()[2].transaction, Some(block.transactions[0].get_hash()));
+
+        assert!(report.to_json()?.get("findings").is_some());
+
+        Ok(())
+    }
+
+    #[test]
+    fn validate_with_clock() -> Result<(), BlockValidationError> {
+        use hyperborealib::crypto::asymmetric::SecretKey;
+
+        use crate::clock::MockClock;
+
+        let block = BlockBuilder::new()
+            .with_clock(&MockClock::new(1000))
+            .sign(&SecretKey::random());
+
+        let context = ValidationContext::default()
+            .with_clock(MockClock::new(900))
+            .with_max_future_drift(100);
+
+        assert!(block.validate_with(&context)?.is_valid());
+
+        let context = context.with_max_future_drift(99);
+
+        assert_eq!(block.validate_with(&context)?, BlockValidationResult::InvalidCreationTime {
+            created_at: 1000
+        });
+
+        Ok(())
+    }
+
+    #[test]
+    fn validate_content() -> Result<(), BlockValidationError> {
+        use hyperborealib::crypto::asymmetric::SecretKey;
+
+        use crate::block::transaction::builder::tests::get_message;
+        use crate::block::minter::tests::get_minter;
+        use crate::clock::MockClock;
+
+        let secret = SecretKey::random();
+
+        let transaction = get_message().0;
+        let minter = get_minter().0;
+
+        // Duplicated transactions and minters
+        let block = BlockBuilder::new()
+            .add_transaction(transaction.clone())
+            .add_transaction(transaction.clone())
+            .add_minter(minter.clone())
+            .add_minter(minter.clone())
+            .sign(&secret);
+
+        let report = block.validate_report();
+
+        assert!(report.findings().iter().any(|finding| {
+            matches!(&finding.issue, ValidationIssue::Block(BlockValidationResult::DuplicateTransaction {
+                transaction: hash
+            }) if *hash == transaction.get_hash())
+        }));
+
+        assert!(report.findings().iter().any(|finding| {
+            matches!(&finding.issue, ValidationIssue::Block(BlockValidationResult::DuplicateMinter {
+                minter: public_key
+            }) if public_key == minter.public_key())
+        }));
+
+        // Limits
+        let context = ValidationContext::default()
+            .with_block_limits(BlockLimits::default().with_max_transactions(1));
+
+        assert_eq!(block.validate_with(&context)?, BlockValidationResult::TooManyTransactions {
+            count: 2,
+            max_transactions: 1
+        });
+
+        let context = ValidationContext::default()
+            .with_block_limits(BlockLimits::default().with_max_fields_size(block.fields_size() - 1));
+
+        assert_eq!(block.validate_with(&context)?, BlockValidationResult::TooLarge {
+            fields_size: block.fields_size(),
+            max_fields_size: block.fields_size() - 1
+        });
+
+        // Transaction created later than the block
+        let block = BlockBuilder::new()
+            .with_clock(&MockClock::new(transaction.created_at() - 1))
+            .add_transaction(transaction.clone())
+            .sign(&secret);
+
+        assert_eq!(block.validate()?, BlockValidationResult::InvalidTransactionCreationTime {
+            transaction: transaction.get_hash(),
+            created_at: transaction.created_at(),
+            block_created_at: transaction.created_at() - 1
+        });
+
+        Ok(())
+    }
+
+    #[test]
+    fn validate_with_parent() -> Result<(), BlockValidationError> {
+        let (root, chained, secret) = get_chained();
+
+        assert!(chained.validate_with_parent(&root)?.is_valid());
+
+        assert_eq!(root.validate_with_parent(&chained)?, BlockValidationResult::InvalidParentNumber {
+            number: 0,
+            parent_number: 1
+        });
+
+        // Block with the same number but another parent
+        let fork = BlockBuilder::chained(&BlockBuilder::build_root(&secret)).sign(&secret);
+
+        assert_eq!(fork.validate_with_parent(&root)?, BlockValidationResult::InvalidParentReference {
+            expected: root.get_hash(),
+            got: fork.previous_block()
+        });
+
+        Ok(())
+    }
+
     #[test]
     fn ord() {
         let (head, tail, _) = get_chained();
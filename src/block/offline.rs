@@ -0,0 +1,504 @@
+use std::path::Path;
+
+use serde_json::{json, Value as Json};
+
+use hyperborealib::exports::tokio;
+
+use hyperborealib::crypto::asymmetric::{SecretKey, PublicKey};
+use hyperborealib::crypto::encoding::base64;
+use hyperborealib::crypto::Error as CryptographyError;
+
+use hyperborealib::rest_api::{
+    AsJson,
+    AsJsonError
+};
+
+use crate::signer::Signer;
+
+use super::prelude::*;
+
+#[derive(Debug, thiserror::Error)]
+pub enum OfflineSigningError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error(transparent)]
+    Json(#[from] AsJsonError),
+
+    #[error(transparent)]
+    Serialize(#[from] serde_json::Error),
+
+    #[error("Failed to verify signature: {0}")]
+    SignVerificationError(#[from] CryptographyError),
+
+    #[error("Failed to sign hash: {0}")]
+    Signer(Box<dyn std::error::Error + Send + Sync>),
+
+    #[error("Stored hash {} doesn't match calculated hash {}", .stored.to_base64(), .calculated.to_base64())]
+    InvalidHash {
+        stored: Hash,
+        calculated: Hash
+    },
+
+    #[error("Signature is made for hash {} instead of {}", .got.to_base64(), .expected.to_base64())]
+    HashMismatch {
+        expected: Hash,
+        got: Hash
+    },
+
+    #[error("Signature is made by {} instead of {}", .got.to_base64(), .expected.to_base64())]
+    SignerMismatch {
+        expected: PublicKey,
+        got: PublicKey
+    },
+
+    #[error("Invalid signature")]
+    InvalidSignature
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Signature of a block or transaction hash
+/// produced on another machine.
+pub struct DetachedSignature {
+    /// Signed hash.
+    pub hash: Hash,
+
+    /// Public key of the signer.
+    pub signer: PublicKey,
+
+    /// Signature of the hash.
+    pub sign: Vec<u8>
+}
+
+impl DetachedSignature {
+    /// Sign given hash with a secret key.
+    pub fn create(hash: Hash, secret: &SecretKey) -> Self {
+        Self {
+            hash,
+            signer: secret.public_key(),
+            sign: secret.create_signature(hash.as_bytes())
+        }
+    }
+
+    /// Sign given hash using the signer.
+    pub async fn create_with<S: Signer>(hash: Hash, signer: &S) -> Result<Self, OfflineSigningError>
+    where
+        S::Error: 'static
+    {
+        Ok(Self {
+            hash,
+            signer: signer.public_key(),
+            sign: signer.sign(&hash).await
+                .map_err(|err| OfflineSigningError::Signer(Box::new(err)))?
+        })
+    }
+
+    /// Verify that the signature is made by the expected
+    /// signer for the expected hash.
+    pub fn verify(&self, hash: &Hash, signer: &PublicKey) -> Result<(), OfflineSigningError> {
+        if &self.hash != hash {
+            return Err(OfflineSigningError::HashMismatch {
+                expected: *hash,
+                got: self.hash
+            });
+        }
+
+        if &self.signer != signer {
+            return Err(OfflineSigningError::SignerMismatch {
+                expected: signer.clone(),
+                got: self.signer.clone()
+            });
+        }
+
+        if !self.signer.verify_signature(self.hash.as_bytes(), &self.sign)? {
+            return Err(OfflineSigningError::InvalidSignature);
+        }
+
+        Ok(())
+    }
+}
+
+impl AsJson for DetachedSignature {
+    fn to_json(&self) -> Result<Json, AsJsonError> {
+        Ok(json!({
+            "format": 1,
+            "signature": {
+                "hash": self.hash.to_base64(),
+                "signer": self.signer.to_base64(),
+                "sign": base64::encode(&self.sign)
+            }
+        }))
+    }
+
+    fn from_json(json: &Json) -> Result<Self, AsJsonError> where Self: Sized {
+        let Some(format) = json.get("format").and_then(Json::as_u64) else {
+            return Err(AsJsonError::FieldNotFound("format"));
+        };
+
+        match format {
+            1 => {
+                let Some(signature) = json.get("signature") else {
+                    return Err(AsJsonError::FieldNotFound("signature"));
+                };
+
+                Ok(Self {
+                    hash: signature.get("hash")
+                        .and_then(Json::as_str)
+                        .map(Hash::from_base64)
+                        .ok_or_else(|| AsJsonError::FieldValueInvalid("signature.hash"))?
+                        .map_err(|err| AsJsonError::Other(err.into()))?,
+
+                    signer: signature.get("signer")
+                        .and_then(Json::as_str)
+                        .map(PublicKey::from_base64)
+                        .ok_or_else(|| AsJsonError::FieldValueInvalid("signature.signer"))??,
+
+                    sign: signature.get("sign")
+                        .and_then(Json::as_str)
+                        .map(base64::decode)
+                        .ok_or_else(|| AsJsonError::FieldValueInvalid("signature.sign"))??
+                })
+            }
+
+            version => Err(AsJsonError::InvalidStandard(version))
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Block with calculated hash which
+/// is waiting for the validator's signature.
+///
+/// ```
+/// use hyperborealib::prelude::*;
+/// use hyperchain::prelude::*;
+///
+/// let secret = SecretKey::random();
+///
+/// // Online machine
+/// let unsigned = BlockBuilder::new()
+///     .prepare(secret.public_key());
+///
+/// // Offline machine
+/// let signature = unsigned.sign(&secret).unwrap();
+///
+/// // Online machine
+/// let block = unsigned.attach(&signature).unwrap();
+///
+/// assert!(block.validate().unwrap().is_valid());
+/// ```
+pub struct UnsignedBlock {
+    pub(crate) block: Block
+}
+
+impl UnsignedBlock {
+    #[inline]
+    /// Hash of the block which should be signed.
+    pub fn hash(&self) -> &Hash {
+        &self.block.hash
+    }
+
+    #[inline]
+    /// Public key of the expected block validator.
+    pub fn validator(&self) -> &PublicKey {
+        &self.block.validator
+    }
+
+    #[inline]
+    /// Unsigned block.
+    pub fn block(&self) -> &Block {
+        &self.block
+    }
+
+    /// Verify that the stored hash is calculated
+    /// from the block's content.
+    ///
+    /// This should be done before signing the block
+    /// since the file could be modified.
+    pub fn verify(&self) -> Result<(), OfflineSigningError> {
+        let calculated = self.block.calculate_hash();
+
+        if self.block.hash != calculated {
+            return Err(OfflineSigningError::InvalidHash {
+                stored: self.block.hash,
+                calculated
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Verify and sign the block's hash.
+    pub fn sign(&self, validator: &SecretKey) -> Result<DetachedSignature, OfflineSigningError> {
+        self.verify()?;
+
+        let signature = DetachedSignature::create(self.block.hash, validator);
+
+        signature.verify(&self.block.hash, &self.block.validator)?;
+
+        Ok(signature)
+    }
+
+    /// Verify and sign the block's hash using the signer.
+    pub async fn sign_with<S: Signer>(&self, signer: &S) -> Result<DetachedSignature, OfflineSigningError>
+    where
+        S::Error: 'static
+    {
+        self.verify()?;
+
+        let signature = DetachedSignature::create_with(self.block.hash, signer).await?;
+
+        signature.verify(&self.block.hash, &self.block.validator)?;
+
+        Ok(signature)
+    }
+
+    /// Verify the signature and attach it to the block.
+    pub fn attach(mut self, signature: &DetachedSignature) -> Result<Block, OfflineSigningError> {
+        self.verify()?;
+
+        signature.verify(&self.block.hash, &self.block.validator)?;
+
+        self.block.sign = signature.sign.clone();
+
+        Ok(self.block)
+    }
+}
+
+impl AsJson for UnsignedBlock {
+    fn to_json(&self) -> Result<Json, AsJsonError> {
+        Ok(json!({
+            "format": 1,
+            "unsigned_block": self.block.to_json()?
+        }))
+    }
+
+    fn from_json(json: &Json) -> Result<Self, AsJsonError> where Self: Sized {
+        let Some(format) = json.get("format").and_then(Json::as_u64) else {
+            return Err(AsJsonError::FieldNotFound("format"));
+        };
+
+        match format {
+            1 => {
+                let Some(block) = json.get("unsigned_block") else {
+                    return Err(AsJsonError::FieldNotFound("unsigned_block"));
+                };
+
+                let mut block = Block::from_json(block)?;
+
+                // Signature is never stored in the unsigned block
+                block.sign = vec![];
+
+                Ok(Self {
+                    block
+                })
+            }
+
+            version => Err(AsJsonError::InvalidStandard(version))
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Transaction with calculated hash which
+/// is waiting for the author's signature.
+pub struct UnsignedTransaction {
+    pub(crate) transaction: Transaction
+}
+
+impl UnsignedTransaction {
+    #[inline]
+    /// Hash of the transaction which should be signed.
+    pub fn hash(&self) -> &Hash {
+        &self.transaction.hash
+    }
+
+    #[inline]
+    /// Public key of the expected transaction author.
+    pub fn author(&self) -> &PublicKey {
+        &self.transaction.author
+    }
+
+    #[inline]
+    /// Unsigned transaction.
+    pub fn transaction(&self) -> &Transaction {
+        &self.transaction
+    }
+
+    /// Verify that the stored hash is calculated
+    /// from the transaction's content.
+    ///
+    /// This should be done before signing the transaction
+    /// since the file could be modified.
+    pub fn verify(&self) -> Result<(), OfflineSigningError> {
+        let calculated = self.transaction.calculate_hash();
+
+        if self.transaction.hash != calculated {
+            return Err(OfflineSigningError::InvalidHash {
+                stored: self.transaction.hash,
+                calculated
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Verify and sign the transaction's hash.
+    pub fn sign(&self, author: &SecretKey) -> Result<DetachedSignature, OfflineSigningError> {
+        self.verify()?;
+
+        let signature = DetachedSignature::create(self.transaction.hash, author);
+
+        signature.verify(&self.transaction.hash, &self.transaction.author)?;
+
+        Ok(signature)
+    }
+
+    /// Verify and sign the transaction's hash using the signer.
+    pub async fn sign_with<S: Signer>(&self, signer: &S) -> Result<DetachedSignature, OfflineSigningError>
+    where
+        S::Error: 'static
+    {
+        self.verify()?;
+
+        let signature = DetachedSignature::create_with(self.transaction.hash, signer).await?;
+
+        signature.verify(&self.transaction.hash, &self.transaction.author)?;
+
+        Ok(signature)
+    }
+
+    /// Verify the signature and attach it to the transaction.
+    pub fn attach(mut self, signature: &DetachedSignature) -> Result<Transaction, OfflineSigningError> {
+        self.verify()?;
+
+        signature.verify(&self.transaction.hash, &self.transaction.author)?;
+
+        self.transaction.sign = signature.sign.clone();
+
+        Ok(self.transaction)
+    }
+}
+
+impl AsJson for UnsignedTransaction {
+    fn to_json(&self) -> Result<Json, AsJsonError> {
+        Ok(json!({
+            "format": 1,
+            "unsigned_transaction": self.transaction.to_json()?
+        }))
+    }
+
+    fn from_json(json: &Json) -> Result<Self, AsJsonError> where Self: Sized {
+        let Some(format) = json.get("format").and_then(Json::as_u64) else {
+            return Err(AsJsonError::FieldNotFound("format"));
+        };
+
+        match format {
+            1 => {
+                let Some(transaction) = json.get("unsigned_transaction") else {
+                    return Err(AsJsonError::FieldNotFound("unsigned_transaction"));
+                };
+
+                let mut transaction = Transaction::from_json(transaction)?;
+
+                // Signature is never stored in the unsigned transaction
+                transaction.sign = vec![];
+
+                Ok(Self {
+                    transaction
+                })
+            }
+
+            version => Err(AsJsonError::InvalidStandard(version))
+        }
+    }
+}
+
+/// Save offline signing artifact (unsigned block,
+/// unsigned transaction or detached signature)
+/// to a JSON file.
+pub async fn write_artifact(path: impl AsRef<Path>, artifact: &impl AsJson) -> Result<(), OfflineSigningError> {
+    let artifact = serde_json::to_string_pretty(&artifact.to_json()?)?;
+
+    tokio::fs::write(path, artifact).await?;
+
+    Ok(())
+}
+
+/// Read offline signing artifact from a JSON file.
+pub async fn read_artifact<T: AsJson>(path: impl AsRef<Path>) -> Result<T, OfflineSigningError> {
+    let artifact = tokio::fs::read(path).await?;
+    let artifact = serde_json::from_slice::<Json>(&artifact)?;
+
+    Ok(T::from_json(&artifact)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::block::transaction::builder::tests::get_message;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn sign_block() -> Result<(), OfflineSigningError> {
+        let path = crate::utils::test_dir("offline-signing-test")?;
+
+        let validator = SecretKey::random();
+
+        let unsigned = BlockBuilder::new()
+            .add_transaction(get_message().0)
+            .prepare(validator.public_key());
+
+        write_artifact(path.join("block.json"), &unsigned).await?;
+
+        // Offline machine
+        let offline = read_artifact::<UnsignedBlock>(path.join("block.json")).await?;
+
+        assert_eq!(offline, unsigned);
+
+        let signature = offline.sign(&validator)?;
+
+        write_artifact(path.join("signature.json"), &signature).await?;
+
+        // Online machine
+        let signature = read_artifact::<DetachedSignature>(path.join("signature.json")).await?;
+
+        // Signature made by another key is rejected
+        let wrong = DetachedSignature::create(*unsigned.hash(), &SecretKey::random());
+
+        assert!(matches!(unsigned.clone().attach(&wrong), Err(OfflineSigningError::SignerMismatch { .. })));
+
+        let block = unsigned.attach(&signature)?;
+
+        assert!(block.validate().unwrap().is_valid());
+
+        tokio::fs::remove_dir_all(&path).await?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn sign_transaction() -> Result<(), OfflineSigningError> {
+        let (transaction, author) = get_message();
+
+        let mut unsigned = TransactionBuilder::new()
+            .with_body(transaction.body().clone())
+            .prepare(author.public_key())
+            .unwrap();
+
+        let signature = unsigned.sign(&author)?;
+
+        // Modified content is detected
+        unsigned.transaction.created_at += 1;
+
+        assert!(matches!(unsigned.sign(&author), Err(OfflineSigningError::InvalidHash { .. })));
+
+        unsigned.transaction.created_at -= 1;
+
+        let transaction = unsigned.attach(&signature)?;
+
+        assert!(transaction.validate().unwrap().is_valid());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,203 @@
+use std::sync::Arc;
+
+use serde::{Serialize, Serializer};
+use serde_json::Value as Json;
+
+use super::prelude::*;
+
+use crate::blockchain::BlockchainValidationResult;
+
+#[derive(Debug)]
+/// Error shared between cloned validation results.
+///
+/// Wrapped errors can't be cloned so they're
+/// compared and serialized by their messages.
+pub struct SharedError<T>(Arc<T>);
+
+impl<T> Clone for SharedError<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T> From<T> for SharedError<T> {
+    #[inline]
+    fn from(error: T) -> Self {
+        Self(Arc::new(error))
+    }
+}
+
+impl<T> std::ops::Deref for SharedError<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: std::fmt::Display> std::fmt::Display for SharedError<T> {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T: std::fmt::Display> PartialEq for SharedError<T> {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0) || self.0.to_string() == other.0.to_string()
+    }
+}
+
+impl<T: std::fmt::Display> Eq for SharedError<T> {}
+
+impl<T: std::fmt::Display> Serialize for SharedError<T> {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0.to_string())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+pub enum ValidationSeverity {
+    /// Data can't be fully validated but
+    /// no rules were violated.
+    Warning,
+
+    /// Validation rule was violated.
+    Error
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub enum ValidationIssue {
+    /// Block validation rule was violated.
+    Block(BlockValidationResult),
+
+    /// Transaction validation rule was violated.
+    Transaction(TransactionValidationResult),
+
+    /// Blockchain structure rule was violated.
+    Blockchain(BlockchainValidationResult),
+
+    /// Failed to validate block.
+    BlockError(SharedError<BlockValidationError>),
+
+    /// Failed to validate transaction.
+    TransactionError(SharedError<TransactionValidationError>),
+
+    /// Head block references a block which is not stored
+    /// so the blockchain can't be fully validated.
+    Truncated {
+        previous_block: Hash
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+/// Single validation report entry.
+pub struct ValidationFinding {
+    /// Number of the block where the issue was found.
+    pub block_number: Option<u64>,
+
+    /// Hash of the transaction where the issue was found.
+    pub transaction: Option<Hash>,
+
+    /// Severity of the issue.
+    pub severity: ValidationSeverity,
+
+    /// Violated rule.
+    pub issue: ValidationIssue
+}
+
+#[derive(Default, Debug, Clone, Serialize)]
+/// Full diagnostic validation report.
+///
+/// Unlike `validate` methods which stop on the first
+/// found problem, report contains all the issues.
+pub struct ValidationReport {
+    findings: Vec<ValidationFinding>,
+    validated_blocks: u64,
+    validated_transactions: u64
+}
+
+impl ValidationReport {
+    #[inline]
+    /// Create empty validation report.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    /// List of all the found issues.
+    pub fn findings(&self) -> &[ValidationFinding] {
+        &self.findings
+    }
+
+    #[inline]
+    /// Amount of validated blocks.
+    pub fn validated_blocks(&self) -> u64 {
+        self.validated_blocks
+    }
+
+    #[inline]
+    /// Amount of validated transactions.
+    pub fn validated_transactions(&self) -> u64 {
+        self.validated_transactions
+    }
+
+    /// Iterate over findings with the given severity.
+    pub fn with_severity(&self, severity: ValidationSeverity) -> impl Iterator<Item = &ValidationFinding> {
+        self.findings.iter()
+            .filter(move |finding| finding.severity == severity)
+    }
+
+    #[inline]
+    /// Check if no validation rules were violated.
+    pub fn is_valid(&self) -> bool {
+        self.with_severity(ValidationSeverity::Error)
+            .next()
+            .is_none()
+    }
+
+    #[inline]
+    /// Serialize report to JSON.
+    pub fn to_json(&self) -> Result<Json, serde_json::Error> {
+        serde_json::to_value(self)
+    }
+
+    #[inline]
+    /// Add new finding to the report.
+    pub fn push(&mut self, finding: ValidationFinding) {
+        self.findings.push(finding);
+    }
+
+    /// Add new error to the report.
+    pub fn push_error(&mut self, block_number: Option<u64>, transaction: Option<Hash>, issue: ValidationIssue) {
+        self.push(ValidationFinding {
+            block_number,
+            transaction,
+            severity: ValidationSeverity::Error,
+            issue
+        });
+    }
+
+    /// Add new warning to the report.
+    pub fn push_warning(&mut self, block_number: Option<u64>, transaction: Option<Hash>, issue: ValidationIssue) {
+        self.push(ValidationFinding {
+            block_number,
+            transaction,
+            severity: ValidationSeverity::Warning,
+            issue
+        });
+    }
+
+    #[inline]
+    pub(crate) fn count_block(&mut self) {
+        self.validated_blocks += 1;
+    }
+
+    #[inline]
+    pub(crate) fn count_transactions(&mut self, amount: u64) {
+        self.validated_transactions += amount;
+    }
+}
@@ -0,0 +1,186 @@
+use std::collections::{HashSet, VecDeque};
+use std::sync::{Mutex, PoisonError};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use hyperborealib::crypto::asymmetric::PublicKey;
+use hyperborealib::crypto::Error as CryptographyError;
+
+use super::hash::Hash;
+
+#[derive(Default, Debug)]
+struct SignatureCacheState {
+    /// Verified signatures.
+    signatures: HashSet<Hash>,
+
+    /// Verified signatures in order of insertion.
+    order: VecDeque<Hash>
+}
+
+#[derive(Debug)]
+/// Bounded cache of verified signatures.
+///
+/// The same blocks and transactions are received
+/// from many peers, so caching verified
+/// `(hash, signer, signature)` triples allows
+/// to skip repeated signature checks.
+///
+/// Only valid signatures are cached. The oldest
+/// ones are removed once the cache is full.
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use hyperchain::prelude::*;
+///
+/// let context = ValidationContext::default()
+///     .with_signature_cache(Arc::new(SignatureCache::default()));
+/// ```
+pub struct SignatureCache {
+    state: Mutex<SignatureCacheState>,
+    capacity: usize,
+    hits: AtomicU64,
+    misses: AtomicU64
+}
+
+impl Default for SignatureCache {
+    #[inline]
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_CAPACITY)
+    }
+}
+
+impl SignatureCache {
+    /// Default maximal amount of stored signatures.
+    pub const DEFAULT_CAPACITY: usize = 4096;
+
+    #[inline]
+    /// Create new cache with given maximal
+    /// amount of stored signatures.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            state: Mutex::new(SignatureCacheState::default()),
+            capacity,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0)
+        }
+    }
+
+    /// Verify signature of the given hash,
+    /// using cached result if available.
+    pub fn verify(&self, hash: &Hash, signer: &PublicKey, sign: &[u8]) -> Result<bool, CryptographyError> {
+        let key = Self::key(hash, signer, sign);
+
+        if self.lock().signatures.contains(&key) {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+
+            return Ok(true);
+        }
+
+        self.misses.fetch_add(1, Ordering::Relaxed);
+
+        let result = signer.verify_signature(hash.as_bytes(), sign)?;
+
+        if result && self.capacity > 0 {
+            let mut state = self.lock();
+
+            if state.signatures.insert(key) {
+                state.order.push_back(key);
+
+                while state.order.len() > self.capacity {
+                    if let Some(key) = state.order.pop_front() {
+                        state.signatures.remove(&key);
+                    }
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    #[inline]
+    /// Amount of signatures stored in the cache.
+    pub fn len(&self) -> usize {
+        self.lock().signatures.len()
+    }
+
+    #[inline]
+    /// Check if the cache is empty.
+    pub fn is_empty(&self) -> bool {
+        self.lock().signatures.is_empty()
+    }
+
+    #[inline]
+    /// Amount of verifications served from the cache.
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    #[inline]
+    /// Amount of actually verified signatures.
+    pub fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    /// Remove all the cached signatures.
+    pub fn clear(&self) {
+        let mut state = self.lock();
+
+        state.signatures.clear();
+        state.order.clear();
+    }
+
+    #[inline]
+    fn lock(&self) -> std::sync::MutexGuard<'_, SignatureCacheState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Commit to the whole verified triple
+    /// to keep cache entries small.
+    fn key(hash: &Hash, signer: &PublicKey, sign: &[u8]) -> Hash {
+        let mut hasher = blake3::Hasher::new();
+
+        hasher.update(&hash.as_bytes());
+        hasher.update(&signer.to_bytes());
+        hasher.update(sign);
+
+        hasher.finalize().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hyperborealib::crypto::asymmetric::SecretKey;
+
+    use super::*;
+
+    #[test]
+    fn verify() -> Result<(), CryptographyError> {
+        let secret = SecretKey::random();
+
+        let hash_a = Hash::hash_slice(b"Hello, World! x1");
+        let hash_b = Hash::hash_slice(b"Hello, World! x2");
+
+        let sign_a = secret.create_signature(hash_a.as_bytes());
+        let sign_b = secret.create_signature(hash_b.as_bytes());
+
+        let cache = SignatureCache::new(1);
+
+        assert!(cache.verify(&hash_a, &secret.public_key(), &sign_a)?);
+        assert!(cache.verify(&hash_a, &secret.public_key(), &sign_a)?);
+
+        assert_eq!((cache.hits(), cache.misses()), (1, 1));
+
+        // Invalid signatures are not cached
+        assert!(!cache.verify(&hash_b, &secret.public_key(), &sign_a)?);
+
+        assert_eq!(cache.len(), 1);
+
+        // Push B, removing A from the cache
+        assert!(cache.verify(&hash_b, &secret.public_key(), &sign_b)?);
+        assert!(cache.verify(&hash_a, &secret.public_key(), &sign_a)?);
+
+        assert_eq!((cache.hits(), cache.misses()), (1, 4));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,244 @@
+use serde::{Serialize, Deserialize};
+
+use chacha20poly1305::{XChaCha20Poly1305, XNonce, Key, KeyInit};
+use chacha20poly1305::aead::Aead;
+
+use hyperborealib::crypto::prelude::*;
+use hyperborealib::crypto::asymmetric::SecretKey;
+use hyperborealib::crypto::compression::CompressionLevel;
+
+use hyperborealib::rest_api::types::{
+    MessageEncoding,
+    MessagesError
+};
+
+use crate::utils::random_bytes;
+
+use super::*;
+
+/// Size of the group message's content key.
+pub(crate) const CONTENT_KEY_SIZE: usize = 32;
+
+/// Size of the wrapped content key's nonce.
+pub(crate) const WRAP_NONCE_SIZE: usize = 24;
+
+#[derive(Debug, thiserror::Error)]
+pub enum GroupMessageTransactionBuildError {
+    #[error(transparent)]
+    Message(#[from] MessagesError),
+
+    #[error("Group message's recipients not specified")]
+    NoRecipients,
+
+    #[error("Group message's format doesn't encrypt the content")]
+    Unencrypted,
+
+    #[error("Failed to wrap content key")]
+    KeyWrap
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct GroupMessageTransactionBuilder {
+    recipients: Vec<PublicKey>,
+    format: MessageEncoding,
+    content: Vec<u8>,
+
+    compress_level: CompressionLevel,
+    encryption_salt: Option<Vec<u8>>
+}
+
+impl Default for GroupMessageTransactionBuilder {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GroupMessageTransactionBuilder {
+    /// Build new `group_message` transaction body.
+    ///
+    /// Message's content is encoded once using
+    /// a random content key. This key is encrypted
+    /// for each recipient using the shared secret
+    /// of the sender and the recipient.
+    ///
+    /// Content is encrypted using ChaCha20-Poly1305
+    /// by default. Formats without encryption
+    /// are rejected.
+    ///
+    /// ```
+    /// use hyperborealib::prelude::*;
+    /// use hyperchain::prelude::*;
+    ///
+    /// let sender = SecretKey::random();
+    /// let alice = SecretKey::random();
+    /// let bob = SecretKey::random();
+    ///
+    /// let transaction_body = GroupMessageTransactionBuilder::new()
+    ///     .with_recipient(alice.public_key())
+    ///     .with_recipient(bob.public_key())
+    ///     .with_content(b"Hello, World!")
+    ///     .build(&sender)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(transaction_body.read_group_message(&bob, None).unwrap(), b"Hello, World!");
+    /// ```
+    pub fn new() -> Self {
+        Self {
+            recipients: vec![],
+            format: MessageEncoding::new(
+                Encoding::Base64,
+                Encryption::ChaCha20Poly1305,
+                Compression::None
+            ),
+            content: vec![],
+
+            compress_level: CompressionLevel::default(),
+            encryption_salt: None
+        }
+    }
+
+    #[inline]
+    /// Add message's recipient.
+    pub fn with_recipient(mut self, recipient: impl Into<PublicKey>) -> Self {
+        let recipient = recipient.into();
+
+        if !self.recipients.contains(&recipient) {
+            self.recipients.push(recipient);
+        }
+
+        self
+    }
+
+    #[inline]
+    /// Add multiple message's recipients.
+    pub fn with_recipients<T: Into<PublicKey>>(self, recipients: impl IntoIterator<Item = T>) -> Self {
+        recipients.into_iter()
+            .fold(self, |builder, recipient| builder.with_recipient(recipient))
+    }
+
+    #[inline]
+    /// Change message's format.
+    pub fn with_format(mut self, format: impl Into<MessageEncoding>) -> Self {
+        self.format = format.into();
+
+        self
+    }
+
+    #[inline]
+    /// Change message's content.
+    pub fn with_content(mut self, content: impl Into<Vec<u8>>) -> Self {
+        self.content = content.into();
+
+        self
+    }
+
+    #[inline]
+    /// Change message's compression level.
+    pub fn with_compression_level(mut self, level: impl Into<CompressionLevel>) -> Self {
+        self.compress_level = level.into();
+
+        self
+    }
+
+    #[inline]
+    /// Change salt of the shared secrets
+    /// used to wrap the content key.
+    pub fn with_encryption_salt(mut self, salt: impl Into<Vec<u8>>) -> Self {
+        self.encryption_salt = Some(salt.into());
+
+        self
+    }
+
+    /// Build `group_message` transaction by signing its content.
+    pub fn build(self, from: &SecretKey) -> Result<TransactionBody, GroupMessageTransactionBuildError> {
+        if self.recipients.is_empty() {
+            return Err(GroupMessageTransactionBuildError::NoRecipients);
+        }
+
+        if self.format.encryption == Encryption::None {
+            return Err(GroupMessageTransactionBuildError::Unencrypted);
+        }
+
+        let content_key = random_bytes(CONTENT_KEY_SIZE);
+
+        let recipients = self.recipients.into_iter()
+            .map(|recipient| {
+                let secret = from.create_shared_secret(&recipient, self.encryption_salt.as_deref());
+
+                let mut wrapped_key = random_bytes(WRAP_NONCE_SIZE);
+
+                let encrypted_key = XChaCha20Poly1305::new(Key::from_slice(&secret))
+                    .encrypt(XNonce::from_slice(&wrapped_key), content_key.as_slice())
+                    .map_err(|_| GroupMessageTransactionBuildError::KeyWrap)?;
+
+                wrapped_key.extend_from_slice(&encrypted_key);
+
+                Ok(GroupRecipient {
+                    key: recipient,
+                    wrapped_key
+                })
+            })
+            .collect::<Result<Vec<_>, GroupMessageTransactionBuildError>>()?;
+
+        Ok(TransactionBody::GroupMessage {
+            from: from.public_key(),
+            recipients,
+            format: self.format,
+            content: self.format.forward(&self.content, &content_key, self.compress_level)?
+        })
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    pub fn get_body() -> (TransactionBody, SecretKey) {
+        let secret = SecretKey::random();
+
+        let transaction = GroupMessageTransactionBuilder::new()
+            .with_recipient(secret.public_key())
+            .with_recipient(SecretKey::random().public_key())
+            .with_content(b"Hello, World!")
+            .build(&secret)
+            .unwrap();
+
+        (transaction, secret)
+    }
+
+    #[test]
+    fn build() {
+        let (transaction, secret) = get_body();
+
+        let TransactionBody::GroupMessage { from, recipients, content, .. } = &transaction else {
+            panic!("Invalid transaction body");
+        };
+
+        assert_eq!(from, &secret.public_key());
+        assert_eq!(recipients.len(), 2);
+
+        assert_eq!(transaction.recipients()[0], &secret.public_key());
+
+        // Content key is wrapped separately for each recipient
+        assert_ne!(recipients[0].wrapped_key(), recipients[1].wrapped_key());
+
+        // Default format encrypts the content
+        assert_ne!(base64::decode(content).as_deref(), Ok(b"Hello, World!".as_slice()));
+
+        assert_eq!(transaction.read_group_message(&secret, None).as_deref().ok(), Some(b"Hello, World!".as_slice()));
+
+        assert!(matches!(
+            GroupMessageTransactionBuilder::new().build(&secret),
+            Err(GroupMessageTransactionBuildError::NoRecipients)
+        ));
+
+        assert!(matches!(
+            GroupMessageTransactionBuilder::new()
+                .with_recipient(secret.public_key())
+                .with_format(MessageEncoding::default())
+                .build(&secret),
+            Err(GroupMessageTransactionBuildError::Unencrypted)
+        ));
+    }
+}
@@ -37,9 +37,10 @@ impl MessageTransactionBuilder {
     /// use hyperchain::prelude::*;
     ///
     /// let secret = SecretKey::random();
+    /// let receiver = SecretKey::random();
     ///
     /// let transaction_body = MessageTransactionBuilder::new()
-    ///     .with_receiver(secret.public_key())
+    ///     .with_receiver(receiver.public_key())
     ///     .with_content(b"Hello, World!")
     ///     .build(&secret);
     /// ```
@@ -119,7 +120,7 @@ pub(crate) mod tests {
         let secret = SecretKey::random();
 
         let transaction = MessageTransactionBuilder::new()
-            .with_receiver(secret.public_key())
+            .with_receiver(SecretKey::random().public_key())
             .with_content(b"Hello, World!")
             .build(&secret)
             .unwrap();
@@ -136,7 +137,7 @@ pub(crate) mod tests {
         };
 
         assert_eq!(from, secret.public_key());
-        assert_eq!(to, secret.public_key());
+        assert_ne!(to, secret.public_key());
 
         // After building transaction's content will be encoded
         // into base64 by default (check out MessageEncoding struct / "format" value)
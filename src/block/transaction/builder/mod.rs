@@ -1,37 +1,142 @@
 use serde::{Serialize, Deserialize};
 
-use hyperborealib::crypto::asymmetric::SecretKey;
+use hyperborealib::crypto::asymmetric::{SecretKey, PublicKey};
 
-use hyperborealib::time::timestamp;
 use hyperborealib::crypto::utils::safe_random_u64;
 
+use crate::clock::{Clock, SystemClock};
+use crate::signer::Signer;
+use crate::block::offline::UnsignedTransaction;
+
 pub(crate) mod message;
 pub(crate) mod announcement;
+pub(crate) mod group_message;
 
 pub use message::*;
 pub use announcement::*;
+pub use group_message::*;
 
 use super::*;
 
 #[derive(Default, Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct TransactionBuilder {
+    #[serde(default = "EncodingFormat::legacy")]
+    format: EncodingFormat,
+
     // Metadata
     random_seed: u64,
     created_at: u64,
+    chain_id: Option<ChainId>,
+
+    #[serde(default)]
+    valid_after: Option<u64>,
+
+    #[serde(default)]
+    expires_at: Option<u64>,
+
+    #[serde(default)]
+    nonce: Option<u64>,
 
     // Body
-    body: Option<TransactionBody>
+    body: Option<TransactionBody>,
+
+    #[serde(default)]
+    references: Vec<Hash>
 }
 
 impl TransactionBuilder {
     pub fn new() -> Self {
         Self {
+            format: EncodingFormat::LATEST,
             random_seed: safe_random_u64(),
-            created_at: timestamp(),
-            body: None
+            created_at: SystemClock.timestamp(),
+            chain_id: None,
+            valid_after: None,
+            expires_at: None,
+            nonce: None,
+            body: None,
+            references: vec![]
         }
     }
 
+    #[inline]
+    /// Set transaction's creation time using the given clock.
+    pub fn with_clock(mut self, clock: &dyn Clock) -> Self {
+        self.created_at = clock.timestamp();
+
+        self
+    }
+
+    #[inline]
+    /// Change transaction's encoding format.
+    ///
+    /// Latest format is used by default.
+    pub fn with_format(mut self, format: EncodingFormat) -> Self {
+        self.format = format;
+
+        self
+    }
+
+    #[inline]
+    /// Bind transaction to the blockchain
+    /// with given identifier.
+    pub fn with_chain_id(mut self, chain_id: ChainId) -> Self {
+        self.chain_id = Some(chain_id);
+
+        self
+    }
+
+    #[inline]
+    /// Forbid including the transaction in blocks
+    /// created earlier than the given UTC time.
+    pub fn with_valid_after(mut self, timestamp: u64) -> Self {
+        self.valid_after = Some(timestamp);
+
+        self
+    }
+
+    #[inline]
+    /// Forbid including the transaction in blocks
+    /// created later than the given UTC time.
+    pub fn with_expires_at(mut self, timestamp: u64) -> Self {
+        self.expires_at = Some(timestamp);
+
+        self
+    }
+
+    #[inline]
+    /// Set transaction's sequence number.
+    ///
+    /// Nonces of the author's transactions must
+    /// increase by one without gaps if the
+    /// blockchain requires them.
+    pub fn with_nonce(mut self, nonce: u64) -> Self {
+        self.nonce = Some(nonce);
+
+        self
+    }
+
+    #[inline]
+    /// Add reference to another transaction,
+    /// e.g. the one this transaction replies to.
+    ///
+    /// References are committed into the
+    /// transaction's hash.
+    pub fn with_reference(mut self, transaction: Hash) -> Self {
+        if !self.references.contains(&transaction) {
+            self.references.push(transaction);
+        }
+
+        self
+    }
+
+    #[inline]
+    /// Add references to multiple transactions.
+    pub fn with_references(self, transactions: impl IntoIterator<Item = Hash>) -> Self {
+        transactions.into_iter()
+            .fold(self, |builder, transaction| builder.with_reference(transaction))
+    }
+
     #[inline]
     /// Change transaction's body.
     pub fn with_body(mut self, body: TransactionBody) -> Self {
@@ -42,22 +147,70 @@ impl TransactionBuilder {
 
     /// Build transaction by signing its content.
     pub fn sign(&mut self, author: &SecretKey) -> Option<Transaction> {
+        let mut transaction = self.unsigned(author.public_key())?;
+
+        transaction.sign = author.create_signature(transaction.hash.as_bytes());
+
+        Some(transaction)
+    }
+
+    /// Build transaction by signing its content
+    /// using the given signer.
+    pub async fn sign_with<S: Signer>(&mut self, signer: &S) -> Result<Option<Transaction>, S::Error> {
+        let Some(mut transaction) = self.unsigned(signer.public_key()) else {
+            return Ok(None);
+        };
+
+        transaction.sign = signer.sign(&transaction.hash).await?;
+
+        Ok(Some(transaction))
+    }
+
+    /// Build transaction with calculated hash which
+    /// can be signed on another machine.
+    pub fn prepare(&mut self, author: PublicKey) -> Option<UnsignedTransaction> {
+        Some(UnsignedTransaction {
+            transaction: self.unsigned(author)?
+        })
+    }
+
+    /// Build transaction with calculated hash
+    /// but without signature.
+    ///
+    /// Return `None` if the body is not set, can't be
+    /// encoded in the transaction's format or has
+    /// a malformed custom type identifier.
+    fn unsigned(&mut self, author: PublicKey) -> Option<Transaction> {
+        let body = self.body.as_ref()?;
+
+        if !body.is_supported_by(self.format) {
+            return None;
+        }
+
+        if let TransactionBody::Custom { type_id, .. } = body {
+            if !TransactionType::is_valid_custom_id(type_id) {
+                return None;
+            }
+        }
+
         let body = self.body.take()?;
 
         let mut transaction = Transaction {
             hash: Hash::default(),
+            format: self.format,
             random_seed: self.random_seed,
             created_at: self.created_at,
-            author: author.public_key(),
+            chain_id: self.chain_id,
+            valid_after: self.valid_after,
+            expires_at: self.expires_at,
+            nonce: self.nonce,
+            author,
             body,
+            references: self.references.clone(),
             sign: vec![]
         };
 
-        let hash = transaction.calculate_hash();
-        let sign = author.create_signature(hash.as_bytes());
-
-        transaction.hash = hash;
-        transaction.sign = sign;
+        transaction.hash = transaction.calculate_hash();
 
         Some(transaction)
     }
@@ -68,10 +221,10 @@ pub(crate) mod tests {
     use super::*;
 
     pub fn get_message() -> (Transaction, SecretKey) {
-        let secret = SecretKey::random();
+        let (body, secret) = message::tests::get_body();
 
         let transaction = TransactionBuilder::new()
-            .with_body(message::tests::get_body().0)
+            .with_body(body)
             .sign(&secret)
             .unwrap();
 
@@ -79,10 +232,21 @@ pub(crate) mod tests {
     }
 
     pub fn get_announcement() -> (Transaction, SecretKey) {
-        let secret = SecretKey::random();
+        let (body, secret) = announcement::tests::get_body();
+
+        let transaction = TransactionBuilder::new()
+            .with_body(body)
+            .sign(&secret)
+            .unwrap();
+
+        (transaction, secret)
+    }
+
+    pub fn get_group_message() -> (Transaction, SecretKey) {
+        let (body, secret) = group_message::tests::get_body();
 
         let transaction = TransactionBuilder::new()
-            .with_body(announcement::tests::get_body().0)
+            .with_body(body)
             .sign(&secret)
             .unwrap();
 
@@ -93,7 +257,8 @@ pub(crate) mod tests {
     fn validate() -> Result<(), TransactionValidationError> {
         let transactions = [
             get_message(),
-            get_announcement()
+            get_announcement(),
+            get_group_message()
         ];
 
         for (transaction, author) in transactions {
@@ -104,4 +269,25 @@ pub(crate) mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn unsupported_body() {
+        let secret = SecretKey::random();
+
+        let (body, _) = group_message::tests::get_body();
+
+        assert!(TransactionBuilder::new()
+            .with_format(EncodingFormat::V1)
+            .with_body(body)
+            .sign(&secret)
+            .is_none());
+
+        assert!(TransactionBuilder::new()
+            .with_body(TransactionBody::Custom {
+                type_id: String::new(),
+                payload: vec![]
+            })
+            .sign(&secret)
+            .is_none());
+    }
 }
@@ -1,3 +1,5 @@
+use std::ops::ControlFlow;
+
 use serde::{Serialize, Deserialize};
 use serde_json::{json, Value as Json};
 
@@ -6,15 +8,23 @@ use hyperborealib::crypto::asymmetric::PublicKey;
 use hyperborealib::crypto::encoding::base64;
 use hyperborealib::crypto::Error as CryptographyError;
 
-use hyperborealib::time::timestamp;
-
 use crate::block::hash::Hash;
+use crate::block::chain_id::ChainId;
+use crate::block::format::{EncodingFormat, FieldsHasher};
+use crate::block::context::ValidationContext;
+use crate::block::report::*;
 
 pub(crate) mod transaction_type;
 pub(crate) mod transaction_body;
+pub(crate) mod rules;
+pub(crate) mod registry;
+pub(crate) mod reader;
 
 pub use transaction_type::*;
 pub use transaction_body::*;
+pub use rules::*;
+pub use registry::*;
+pub use reader::*;
 
 pub mod builder;
 
@@ -24,6 +34,14 @@ pub mod prelude {
         TransactionValidationResult,
         TransactionType,
         TransactionBody,
+        GroupRecipient,
+        TransactionBodyRule,
+        TransactionBodyRules,
+        TransactionTypeError,
+        TransactionTypeHandler,
+        TransactionTypeRegistry,
+        CustomTransaction,
+        MessageReadError,
         Transaction
     };
 
@@ -39,7 +57,7 @@ pub enum TransactionValidationError {
     HashCalculationError(#[from] std::io::Error)
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, std::hash::Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, std::hash::Hash, Serialize, Deserialize)]
 pub enum TransactionValidationResult {
     /// Invalid creation timestamp.
     InvalidCreationTime {
@@ -58,6 +76,91 @@ pub enum TransactionValidationResult {
         sign: Vec<u8>
     },
 
+    /// Transaction is made for another blockchain.
+    InvalidChainId {
+        expected: Option<ChainId>,
+        got: Option<ChainId>
+    },
+
+    /// Transaction's body is sent not by its author.
+    InvalidSender {
+        author: PublicKey,
+        from: PublicKey
+    },
+
+    /// Transaction's content is empty.
+    EmptyContent,
+
+    /// Transaction's content can't be decoded
+    /// from its text encoding.
+    UndecodableContent,
+
+    /// Message has no receiver other than its sender.
+    NoReceiver,
+
+    /// Group message has no recipients.
+    NoRecipients,
+
+    /// Transaction's content is too large.
+    ContentTooLarge {
+        size: usize,
+        max_size: usize
+    },
+
+    /// Custom transaction body rule is violated.
+    RuleViolation {
+        rule: String,
+        reason: String
+    },
+
+    /// Transaction expires before it becomes valid.
+    InvalidLifetime {
+        valid_after: u64,
+        expires_at: u64
+    },
+
+    /// Transaction is not valid yet at the reference time.
+    NotYetValid {
+        valid_after: u64,
+        reference_time: u64
+    },
+
+    /// Transaction is expired at the reference time.
+    Expired {
+        expires_at: u64,
+        reference_time: u64
+    },
+
+    /// Transaction has no nonce while
+    /// the blockchain requires it.
+    MissingNonce,
+
+    /// Transaction's body can't be hashed unambiguously
+    /// using the transaction's encoding format.
+    UnsupportedFormat {
+        format: EncodingFormat,
+        transaction_type: TransactionType
+    },
+
+    /// Custom transaction's type identifier
+    /// is empty or malformed.
+    InvalidTransactionType {
+        type_id: String
+    },
+
+    /// Custom transaction's type is not registered
+    /// while the blockchain rejects unknown types.
+    UnknownTransactionType {
+        type_id: String
+    },
+
+    /// Custom transaction's payload is rejected
+    /// by its type handler.
+    InvalidCustomPayload {
+        type_id: String,
+        reason: String
+    },
+
     Valid
 }
 
@@ -73,23 +176,90 @@ pub struct Transaction {
     // Header
     pub(crate) hash: Hash,
 
+    #[serde(default = "EncodingFormat::legacy")]
+    pub(crate) format: EncodingFormat,
+
     // Metadata
     pub(crate) random_seed: u64,
     pub(crate) created_at: u64,
+    pub(crate) chain_id: Option<ChainId>,
+
+    #[serde(default)]
+    pub(crate) valid_after: Option<u64>,
+
+    #[serde(default)]
+    pub(crate) expires_at: Option<u64>,
+
+    #[serde(default)]
+    pub(crate) nonce: Option<u64>,
 
     // Body
     pub(crate) author: PublicKey,
     pub(crate) body: TransactionBody,
+
+    #[serde(default)]
+    pub(crate) references: Vec<Hash>,
+
     pub(crate) sign: Vec<u8>
 }
 
 impl Transaction {
+    /// Prefix of the hashed data of
+    /// transactions with chain ID.
+    pub const HASH_DOMAIN: &'static [u8] = b"hyperchain/transaction";
+
+    /// Prefix of the hashed data of transactions'
+    /// bodies with referenced transactions.
+    pub const REFERENCES_HASH_DOMAIN: &'static [u8] = b"hyperchain/transaction/references";
+
+    #[inline]
+    /// Get transaction's encoding format.
+    pub fn format(&self) -> EncodingFormat {
+        self.format
+    }
+
     #[inline]
     /// Get transaction's UTC creation time.
     pub fn created_at(&self) -> u64 {
         self.created_at
     }
 
+    #[inline]
+    /// Get identifier of the blockchain
+    /// this transaction is made for.
+    pub fn chain_id(&self) -> Option<ChainId> {
+        self.chain_id
+    }
+
+    #[inline]
+    /// Get UTC time since which the
+    /// transaction can be included in a block.
+    pub fn valid_after(&self) -> Option<u64> {
+        self.valid_after
+    }
+
+    #[inline]
+    /// Get UTC time after which the transaction
+    /// can't be included in a block.
+    pub fn expires_at(&self) -> Option<u64> {
+        self.expires_at
+    }
+
+    #[inline]
+    /// Get transaction's sequence number
+    /// among all the author's transactions.
+    pub fn nonce(&self) -> Option<u64> {
+        self.nonce
+    }
+
+    #[inline]
+    /// Check if the transaction is expired
+    /// at the given UTC time.
+    pub fn is_expired_at(&self, timestamp: u64) -> bool {
+        self.expires_at.map(|expires_at| timestamp > expires_at)
+            .unwrap_or(false)
+    }
+
     #[inline]
     /// Get transaction's author.
     pub fn author(&self) -> &PublicKey {
@@ -102,12 +272,40 @@ impl Transaction {
         &self.body
     }
 
+    #[inline]
+    /// Get hashes of the transactions this
+    /// transaction refers to (e.g. replies to
+    /// or edits).
+    pub fn references(&self) -> &[Hash] {
+        &self.references
+    }
+
     #[inline]
     /// Get transaction's sign.
     pub fn sign(&self) -> &[u8] {
         &self.sign
     }
 
+    /// Get total size of the transaction's fields in bytes.
+    ///
+    /// Fields are counted in their raw binary form,
+    /// without the serialized transaction's overhead.
+    pub fn fields_size(&self) -> usize {
+        let optional_fields = [self.valid_after, self.expires_at, self.nonce]
+            .into_iter()
+            .flatten()
+            .count();
+
+        let chain_id = if self.chain_id.is_some() { Hash::BYTES } else { 0 };
+
+        // Hash, random seed and creation time
+        Hash::BYTES + 16 + chain_id + optional_fields * 8 +
+            self.author.to_bytes().len() +
+            self.body.size() +
+            self.references.len() * Hash::BYTES +
+            self.sign.len()
+    }
+
     #[inline]
     /// Get hash stored in the transaction.
     ///
@@ -121,63 +319,311 @@ impl Transaction {
     ///
     /// This is a relatively heavy function and
     /// it should not be called often.
+    ///
+    /// Lifetime, nonce and references are hashed only
+    /// if they're set, so transactions which don't use
+    /// them keep the hashes they had before these
+    /// fields were introduced.
     pub fn calculate_hash(&self) -> Hash {
+        match self.format {
+            EncodingFormat::V1 => self.calculate_hash_v1(),
+            EncodingFormat::V2 => self.calculate_hash_v2()
+        }
+    }
+
+    fn calculate_hash_v1(&self) -> Hash {
         let mut hasher = blake3::Hasher::new();
 
+        // Domain separation for transactions
+        // bound to a blockchain
+        if let Some(chain_id) = &self.chain_id {
+            hasher.update(Self::HASH_DOMAIN);
+            hasher.update(&chain_id.hash().as_bytes());
+        }
+
         hasher.update(&self.random_seed.to_be_bytes());
         hasher.update(&self.author.to_bytes());
-        hasher.update(&self.body.hash().as_bytes());
+        hasher.update(&self.calculate_body_hash(EncodingFormat::V1).as_bytes());
+
+        if self.valid_after.is_some() || self.expires_at.is_some() {
+            hasher.update(b"lifetime");
+            hasher.update(&tagged(self.valid_after));
+            hasher.update(&tagged(self.expires_at));
+        }
+
+        if let Some(nonce) = self.nonce {
+            hasher.update(b"nonce");
+            hasher.update(&nonce.to_be_bytes());
+        }
 
         hasher.finalize().into()
     }
 
+    fn calculate_hash_v2(&self) -> Hash {
+        let mut hasher = FieldsHasher::new(Self::HASH_DOMAIN, EncodingFormat::V2);
+
+        // Empty field for transactions without chain ID
+        match &self.chain_id {
+            Some(chain_id) => hasher.field(chain_id.hash().as_bytes()),
+            None => hasher.field(b"")
+        };
+
+        hasher.field(self.random_seed.to_be_bytes())
+            .field(self.created_at.to_be_bytes())
+            .field(self.author.to_bytes())
+            .field(self.calculate_body_hash(EncodingFormat::V2).as_bytes());
+
+        if self.valid_after.is_some() || self.expires_at.is_some() {
+            hasher.field(b"lifetime")
+                .field(tagged(self.valid_after))
+                .field(tagged(self.expires_at));
+        }
+
+        if let Some(nonce) = self.nonce {
+            hasher.field(b"nonce")
+                .field(nonce.to_be_bytes());
+        }
+
+        hasher.finalize()
+    }
+
+    /// Calculate hash of the transaction's body
+    /// with committed references.
+    fn calculate_body_hash(&self, format: EncodingFormat) -> Hash {
+        let hash = self.body.hash_with_format(format);
+
+        if self.references.is_empty() {
+            return hash;
+        }
+
+        let mut hasher = FieldsHasher::new(Self::REFERENCES_HASH_DOMAIN, EncodingFormat::V2);
+
+        hasher.field(hash.as_bytes())
+            .field((self.references.len() as u64).to_be_bytes());
+
+        for reference in &self.references {
+            hasher.field(reference.as_bytes());
+        }
+
+        hasher.finalize()
+    }
+
     /// Validate transaction.
     ///
     /// This method will:
     ///
     /// 1. Verify that the transaction's creation time
-    ///    is not higher than the current UTC time.
+    ///    is not higher than the current UTC time
+    ///    (+24h to allow clocks drift).
+    ///
+    /// 2. Verify that the transaction is made
+    ///    for the expected blockchain.
+    ///
+    /// 3. Verify that the transaction is valid and
+    ///    not expired at the current UTC time.
     ///
-    /// 2. Calculate transaction hash and compare it
+    /// 4. Verify transaction's body semantic rules
+    ///    and custom transaction's payload.
+    ///
+    /// 5. Calculate transaction hash and compare it
     ///    with stored value.
     ///
-    /// 3. Verify transaction's signature.
+    /// 6. Verify transaction's signature.
     ///
     /// This is not recommended to call this method often.
+    #[inline]
     pub fn validate(&self) -> Result<TransactionValidationResult, TransactionValidationError> {
-        // Validate transaction's creation time (+24h just in case)
-        if self.created_at > timestamp() + 24 * 60 * 60 {
-            return Ok(TransactionValidationResult::InvalidCreationTime {
+        self.validate_with(&ValidationContext::default())
+    }
+
+    /// Validate transaction using given validation context.
+    ///
+    /// Same as `validate` but uses the context's
+    /// clock and future drift tolerance to verify
+    /// the transaction's creation time, the context's
+    /// chain ID, body rules and custom transaction types.
+    #[inline]
+    pub fn validate_with(&self, context: &ValidationContext) -> Result<TransactionValidationResult, TransactionValidationError> {
+        self.validate_at(context, context.clock.timestamp())
+    }
+
+    /// Validate transaction using given validation context
+    /// and verify its lifetime at the given reference time.
+    ///
+    /// Transactions stored in blocks should be validated
+    /// at their block's creation time.
+    pub fn validate_at(&self, context: &ValidationContext, reference_time: u64) -> Result<TransactionValidationResult, TransactionValidationError> {
+        let mut result = Ok(TransactionValidationResult::Valid);
+
+        let _ = self.check_rules(context, reference_time, &mut |issue| {
+            result = issue;
+
+            ControlFlow::Break(())
+        });
+
+        result
+    }
+
+    /// Validate transaction, collecting all the found
+    /// issues in a new report.
+    ///
+    /// Unlike `validate` this method will not stop
+    /// on the first found problem.
+    #[inline]
+    pub fn validate_report(&self) -> ValidationReport {
+        self.validate_report_with(&ValidationContext::default())
+    }
+
+    /// Validate transaction using given validation context,
+    /// collecting all the found issues in a new report.
+    pub fn validate_report_with(&self, context: &ValidationContext) -> ValidationReport {
+        let mut report = ValidationReport::new();
+
+        self.report_issues(context, context.clock.timestamp(), None, &mut report);
+
+        report
+    }
+
+    /// Store all the transaction's issues in the given report.
+    pub(crate) fn report_issues(&self, context: &ValidationContext, reference_time: u64, block_number: Option<u64>, report: &mut ValidationReport) {
+        let _ = self.check_rules(context, reference_time, &mut |issue| {
+            let issue = match issue {
+                Ok(result) => ValidationIssue::Transaction(result),
+                Err(err) => ValidationIssue::TransactionError(err.into())
+            };
+
+            report.push_error(block_number, Some(self.hash), issue);
+
+            ControlFlow::Continue(())
+        });
+
+        report.count_transactions(1);
+    }
+
+    /// Verify all the transaction's rules, passing violated
+    /// ones to the callback until it asks to stop.
+    ///
+    /// Both `validate_at` and `report_issues` are built
+    /// on top of this method so they can't diverge.
+    pub(crate) fn check_rules(
+        &self,
+        context: &ValidationContext,
+        reference_time: u64,
+        issue: &mut impl FnMut(Result<TransactionValidationResult, TransactionValidationError>) -> ControlFlow<()>
+    ) -> ControlFlow<()> {
+        // Validate transaction's creation time
+        if self.created_at > context.max_timestamp() {
+            issue(Ok(TransactionValidationResult::InvalidCreationTime {
                 created_at: self.created_at
-            });
+            }))?;
+        }
+
+        // Validate transaction's chain ID
+        if !context.is_chain_id_allowed(self.chain_id) {
+            issue(Ok(TransactionValidationResult::InvalidChainId {
+                expected: context.chain_id,
+                got: self.chain_id
+            }))?;
+        }
+
+        // Validate transaction's lifetime
+        if let Some(result) = self.check_lifetime(reference_time) {
+            issue(Ok(result))?;
+        }
+
+        // Validate transaction's nonce
+        if context.require_nonces && self.nonce.is_none() {
+            issue(Ok(TransactionValidationResult::MissingNonce))?;
+        }
+
+        // Validate transaction's body format
+        if !self.body.is_supported_by(self.format) {
+            issue(Ok(TransactionValidationResult::UnsupportedFormat {
+                format: self.format,
+                transaction_type: self.body.transaction_type()
+            }))?;
+        }
+
+        // Validate transaction's body
+        for result in context.body_rules.check_all(&self.author, &self.body) {
+            issue(Ok(result))?;
+        }
+
+        // Validate custom transaction's payload
+        if let Some(result) = context.transaction_types.check(&self.author, &self.body) {
+            issue(Ok(result))?;
         }
 
         // Validate transaction's hash
         let hash = self.calculate_hash();
 
         if self.hash != hash {
-            return Ok(TransactionValidationResult::InvalidHash {
+            issue(Ok(TransactionValidationResult::InvalidHash {
                 stored: self.hash,
                 calculated: hash
-            });
+            }))?;
         }
 
         // Validate transaction hash's signature
-        if !self.author.verify_signature(self.hash.as_bytes(), &self.sign)? {
-            return Ok(TransactionValidationResult::InvalidSign {
+        match context.verify_signature(&self.hash, &self.author, &self.sign) {
+            Ok(true) => ControlFlow::Continue(()),
+
+            Ok(false) => issue(Ok(TransactionValidationResult::InvalidSign {
                 hash: self.hash,
                 sign: self.sign.clone()
-            });
+            })),
+
+            Err(err) => issue(Err(err.into()))
+        }
+    }
+
+    /// Verify that the transaction can be
+    /// used at the given reference time.
+    fn check_lifetime(&self, reference_time: u64) -> Option<TransactionValidationResult> {
+        if let (Some(valid_after), Some(expires_at)) = (self.valid_after, self.expires_at) {
+            if expires_at < valid_after {
+                return Some(TransactionValidationResult::InvalidLifetime {
+                    valid_after,
+                    expires_at
+                });
+            }
         }
 
-        Ok(TransactionValidationResult::Valid)
+        if let Some(valid_after) = self.valid_after {
+            if reference_time < valid_after {
+                return Some(TransactionValidationResult::NotYetValid {
+                    valid_after,
+                    reference_time
+                });
+            }
+        }
+
+        if let Some(expires_at) = self.expires_at {
+            if reference_time > expires_at {
+                return Some(TransactionValidationResult::Expired {
+                    expires_at,
+                    reference_time
+                });
+            }
+        }
+
+        None
+    }
+}
+
+/// Encode optional value with an explicit presence
+/// tag so `None` can't be confused with any value.
+fn tagged(value: Option<u64>) -> Vec<u8> {
+    match value {
+        Some(value) => [&[1], value.to_be_bytes().as_slice()].concat(),
+        None => vec![0]
     }
 }
 
 impl AsJson for Transaction {
     fn to_json(&self) -> Result<Json, AsJsonError> {
-        Ok(json!({
-            "format": 1,
+        let mut json = json!({
+            "format": self.format.version(),
             "transaction": {
                 "hash": self.hash.to_base64(),
                 "metadata": {
@@ -190,7 +636,32 @@ impl AsJson for Transaction {
                     "sign": base64::encode(&self.sign)
                 }
             }
-        }))
+        });
+
+        // Keep JSON of transactions without chain ID unchanged
+        if let Some(chain_id) = &self.chain_id {
+            json["transaction"]["metadata"]["chain_id"] = Json::String(chain_id.to_base64());
+        }
+
+        if let Some(valid_after) = self.valid_after {
+            json["transaction"]["metadata"]["valid_after"] = Json::from(valid_after);
+        }
+
+        if let Some(expires_at) = self.expires_at {
+            json["transaction"]["metadata"]["expires_at"] = Json::from(expires_at);
+        }
+
+        if let Some(nonce) = self.nonce {
+            json["transaction"]["metadata"]["nonce"] = Json::from(nonce);
+        }
+
+        if !self.references.is_empty() {
+            json["transaction"]["content"]["references"] = self.references.iter()
+                .map(|reference| Json::String(reference.to_base64()))
+                .collect();
+        }
+
+        Ok(json)
     }
 
     fn from_json(json: &Json) -> Result<Self, AsJsonError> where Self: Sized {
@@ -199,7 +670,7 @@ impl AsJson for Transaction {
         };
 
         match format {
-            1 => {
+            1 | 2 => {
                 let Some(transaction) = json.get("transaction") else {
                     return Err(AsJsonError::FieldNotFound("transaction"));
                 };
@@ -219,6 +690,9 @@ impl AsJson for Transaction {
                         .ok_or_else(|| AsJsonError::FieldValueInvalid("transaction.hash"))?
                         .map_err(|err| AsJsonError::Other(err.into()))?,
 
+                    format: EncodingFormat::from_version(format)
+                        .ok_or(AsJsonError::InvalidStandard(format))?,
+
                     random_seed: metadata.get("random_seed")
                         .and_then(Json::as_u64)
                         .ok_or_else(|| AsJsonError::FieldValueInvalid("transaction.metadata.random_seed"))?,
@@ -227,6 +701,36 @@ impl AsJson for Transaction {
                         .and_then(Json::as_u64)
                         .ok_or_else(|| AsJsonError::FieldValueInvalid("transaction.metadata.created_at"))?,
 
+                    chain_id: match metadata.get("chain_id") {
+                        None | Some(Json::Null) => None,
+
+                        Some(chain_id) => Some(chain_id.as_str()
+                            .map(ChainId::from_base64)
+                            .ok_or_else(|| AsJsonError::FieldValueInvalid("transaction.metadata.chain_id"))?
+                            .map_err(|err| AsJsonError::Other(err.into()))?)
+                    },
+
+                    valid_after: match metadata.get("valid_after") {
+                        None | Some(Json::Null) => None,
+
+                        Some(valid_after) => Some(valid_after.as_u64()
+                            .ok_or_else(|| AsJsonError::FieldValueInvalid("transaction.metadata.valid_after"))?)
+                    },
+
+                    expires_at: match metadata.get("expires_at") {
+                        None | Some(Json::Null) => None,
+
+                        Some(expires_at) => Some(expires_at.as_u64()
+                            .ok_or_else(|| AsJsonError::FieldValueInvalid("transaction.metadata.expires_at"))?)
+                    },
+
+                    nonce: match metadata.get("nonce") {
+                        None | Some(Json::Null) => None,
+
+                        Some(nonce) => Some(nonce.as_u64()
+                            .ok_or_else(|| AsJsonError::FieldValueInvalid("transaction.metadata.nonce"))?)
+                    },
+
                     author: content.get("author")
                         .and_then(Json::as_str)
                         .map(PublicKey::from_base64)
@@ -236,6 +740,21 @@ impl AsJson for Transaction {
                         .ok_or_else(|| AsJsonError::FieldValueInvalid("transaction.content.body"))
                         .and_then(TransactionBody::from_json)?,
 
+                    references: match content.get("references") {
+                        None | Some(Json::Null) => vec![],
+
+                        Some(references) => references.as_array()
+                            .ok_or_else(|| AsJsonError::FieldValueInvalid("transaction.content.references"))?
+                            .iter()
+                            .map(|reference| {
+                                reference.as_str()
+                                    .map(Hash::from_base64)
+                                    .ok_or_else(|| AsJsonError::FieldValueInvalid("transaction.content.references"))?
+                                    .map_err(|err| AsJsonError::Other(err.into()))
+                            })
+                            .collect::<Result<Vec<_>, _>>()?
+                    },
+
                     sign: content.get("sign")
                         .and_then(Json::as_str)
                         .map(base64::decode)
@@ -270,4 +789,221 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn chain_id() -> Result<(), TransactionValidationError> {
+        use hyperborealib::crypto::asymmetric::SecretKey;
+
+        use crate::block::transaction::builder::TransactionBuilder;
+
+        let mainnet = ChainId::from_name("mainnet");
+        let testnet = ChainId::from_name("testnet");
+
+        let transaction = TransactionBuilder::new()
+            .with_chain_id(mainnet)
+            .with_body(TransactionBody::Raw(b"Hello, World!".to_vec()))
+            .sign(&SecretKey::random())
+            .unwrap();
+
+        assert_eq!(Transaction::from_json(&transaction.to_json().unwrap()).unwrap(), transaction);
+
+        assert!(transaction.validate()?.is_valid());
+        assert!(transaction.validate_with(&ValidationContext::default().with_chain_id(mainnet))?.is_valid());
+
+        assert_eq!(transaction.validate_with(&ValidationContext::default().with_chain_id(testnet))?, TransactionValidationResult::InvalidChainId {
+            expected: Some(testnet),
+            got: Some(mainnet)
+        });
+
+        // Chain ID can't be changed without breaking the hash
+        let mut replayed = transaction.clone();
+
+        replayed.chain_id = Some(testnet);
+
+        assert_ne!(replayed.calculate_hash(), transaction.get_hash());
+
+        // Transactions without chain ID are rejected
+        // unless explicitly allowed
+        let unbound = TransactionBuilder::new()
+            .with_body(TransactionBody::Raw(b"Hello, World!".to_vec()))
+            .sign(&SecretKey::random())
+            .unwrap();
+
+        assert_eq!(unbound.validate_with(&ValidationContext::default().with_chain_id(mainnet))?, TransactionValidationResult::InvalidChainId {
+            expected: Some(mainnet),
+            got: None
+        });
+
+        assert!(unbound.validate_with(&ValidationContext::default().with_chain_id(mainnet).with_allowed_unbound(true))?.is_valid());
+
+        Ok(())
+    }
+
+    #[test]
+    fn formats() -> Result<(), AsJsonError> {
+        use hyperborealib::crypto::asymmetric::SecretKey;
+
+        use crate::block::transaction::builder::TransactionBuilder;
+
+        let secret = SecretKey::random();
+
+        for format in [EncodingFormat::V1, EncodingFormat::V2] {
+            let transaction = TransactionBuilder::new()
+                .with_format(format)
+                .with_body(TransactionBody::Raw(b"Hello, World!".to_vec()))
+                .sign(&secret)
+                .unwrap();
+
+            let json = transaction.to_json()?;
+
+            assert_eq!(json.get("format").and_then(Json::as_u64), Some(format.version()));
+            assert_eq!(Transaction::from_json(&json)?, transaction);
+
+            assert!(transaction.validate().unwrap().is_valid());
+
+            // Only the second format commits the creation time
+            let mut relayed = transaction.clone();
+
+            relayed.created_at += 1;
+
+            assert_eq!(relayed.calculate_hash() == transaction.get_hash(), format == EncodingFormat::V1);
+        }
+
+        // Custom bodies can share their hash with
+        // raw ones in the first format
+        let mut transaction = TransactionBuilder::new()
+            .with_body(TransactionBody::Custom {
+                type_id: String::from("vote"),
+                payload: b"Hello, World!".to_vec()
+            })
+            .sign(&secret)
+            .unwrap();
+
+        transaction.format = EncodingFormat::V1;
+        transaction.hash = transaction.calculate_hash();
+        transaction.sign = secret.create_signature(transaction.hash.as_bytes());
+
+        assert_eq!(transaction.validate().unwrap(), TransactionValidationResult::UnsupportedFormat {
+            format: EncodingFormat::V1,
+            transaction_type: TransactionType::Custom(String::from("vote"))
+        });
+
+        Ok(())
+    }
+
+    #[test]
+    fn lifetime() -> Result<(), TransactionValidationError> {
+        use hyperborealib::crypto::asymmetric::SecretKey;
+
+        use crate::clock::MockClock;
+        use crate::block::builder::BlockBuilder;
+        use crate::block::transaction::builder::TransactionBuilder;
+
+        let secret = SecretKey::random();
+
+        let transaction = TransactionBuilder::new()
+            .with_clock(&MockClock::new(1000))
+            .with_valid_after(1000)
+            .with_expires_at(2000)
+            .with_body(TransactionBody::Raw(b"Hello, World!".to_vec()))
+            .sign(&secret)
+            .unwrap();
+
+        assert_eq!(Transaction::from_json(&transaction.to_json().unwrap()).unwrap(), transaction);
+
+        let context = ValidationContext::default();
+
+        assert!(transaction.validate_at(&context, 1500)?.is_valid());
+
+        assert_eq!(transaction.validate_at(&context, 999)?, TransactionValidationResult::NotYetValid {
+            valid_after: 1000,
+            reference_time: 999
+        });
+
+        assert_eq!(transaction.validate_at(&context, 2001)?, TransactionValidationResult::Expired {
+            expires_at: 2000,
+            reference_time: 2001
+        });
+
+        // Lifetime can't be changed without breaking the hash
+        let mut extended = transaction.clone();
+
+        extended.expires_at = Some(3000);
+
+        assert_ne!(extended.calculate_hash(), transaction.get_hash());
+
+        // Missing bounds differ from the extreme values
+        for format in [EncodingFormat::V1, EncodingFormat::V2] {
+            let mut bounded = transaction.clone();
+
+            bounded.format = format;
+            bounded.valid_after = None;
+
+            let mut unbounded = bounded.clone();
+
+            unbounded.valid_after = Some(0);
+
+            assert_ne!(bounded.calculate_hash(), unbounded.calculate_hash());
+
+            bounded.valid_after = Some(1000);
+            bounded.expires_at = None;
+
+            unbounded.valid_after = Some(1000);
+            unbounded.expires_at = Some(u64::MAX);
+
+            assert_ne!(bounded.calculate_hash(), unbounded.calculate_hash());
+        }
+
+        // Blocks validate transactions at their creation time
+        let block = BlockBuilder::new()
+            .with_clock(&MockClock::new(1500))
+            .add_transaction(transaction.clone())
+            .sign(&secret);
+
+        assert!(block.validate().unwrap().is_valid());
+
+        let block = BlockBuilder::new()
+            .with_clock(&MockClock::new(2500))
+            .add_transaction(transaction)
+            .sign(&secret);
+
+        assert!(!block.validate().unwrap().is_valid());
+
+        Ok(())
+    }
+
+    #[test]
+    fn references() -> Result<(), AsJsonError> {
+        use hyperborealib::crypto::asymmetric::SecretKey;
+
+        use crate::block::transaction::builder::TransactionBuilder;
+
+        let secret = SecretKey::random();
+
+        let reference = Hash::hash_slice(b"Hello, World!");
+
+        for format in [EncodingFormat::V1, EncodingFormat::V2] {
+            let transaction = TransactionBuilder::new()
+                .with_format(format)
+                .with_reference(reference)
+                .with_reference(reference)
+                .with_body(TransactionBody::Raw(b"Hello, World!".to_vec()))
+                .sign(&secret)
+                .unwrap();
+
+            assert_eq!(transaction.references(), &[reference]);
+            assert_eq!(Transaction::from_json(&transaction.to_json()?)?, transaction);
+
+            assert!(transaction.validate().unwrap().is_valid());
+
+            // References can't be changed without breaking the hash
+            let mut edited = transaction.clone();
+
+            edited.references.clear();
+
+            assert_ne!(edited.calculate_hash(), transaction.get_hash());
+        }
+
+        Ok(())
+    }
 }
@@ -0,0 +1,296 @@
+use chacha20poly1305::{XChaCha20Poly1305, XNonce, Key, KeyInit};
+use chacha20poly1305::aead::Aead;
+
+use hyperborealib::crypto::prelude::*;
+use hyperborealib::crypto::asymmetric::SecretKey;
+
+use hyperborealib::rest_api::types::{
+    MessageEncoding,
+    MessagesError
+};
+
+use super::builder::group_message::WRAP_NONCE_SIZE;
+use super::*;
+
+#[derive(Debug, thiserror::Error)]
+pub enum MessageReadError {
+    #[error("Expected {expected} transaction body, got {got}")]
+    WrongType {
+        expected: TransactionType,
+        got: TransactionType
+    },
+
+    #[error("Key doesn't belong to any participant of the message")]
+    NotParticipant,
+
+    #[error("Announcement is encrypted and can be read only by its author")]
+    EncryptedAnnouncement,
+
+    #[error("Failed to unwrap group message's content key (wrong key or salt)")]
+    KeyUnwrap,
+
+    #[error("Message content can't be decoded from its text encoding")]
+    InvalidEncoding,
+
+    #[error("Failed to decrypt message content (wrong key or salt): {0}")]
+    Decryption(#[source] MessagesError)
+}
+
+/// Decode message's content using shared secret.
+fn read_content(format: &MessageEncoding, content: &str, secret: &[u8]) -> Result<Vec<u8>, MessageReadError> {
+    if format.encoding.decode(content).is_err() {
+        return Err(MessageReadError::InvalidEncoding);
+    }
+
+    format.backward(content, secret)
+        .map_err(MessageReadError::Decryption)
+}
+
+impl TransactionBody {
+    /// Read content of the `message` transaction.
+    ///
+    /// Message can be read both by its sender
+    /// and receiver. The salt must be the same
+    /// as used to build the message.
+    ///
+    /// ```
+    /// use hyperborealib::prelude::*;
+    /// use hyperchain::prelude::*;
+    ///
+    /// let sender = SecretKey::random();
+    /// let receiver = SecretKey::random();
+    ///
+    /// let body = MessageTransactionBuilder::new()
+    ///     .with_receiver(receiver.public_key())
+    ///     .with_content(b"Hello, World!")
+    ///     .build(&sender)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(body.read_message(&receiver, None).unwrap(), b"Hello, World!");
+    /// assert_eq!(body.read_message(&sender, None).unwrap(), b"Hello, World!");
+    /// ```
+    pub fn read_message(&self, reader: &SecretKey, salt: Option<&[u8]>) -> Result<Vec<u8>, MessageReadError> {
+        let Self::Message { from, to, format, content } = self else {
+            return Err(MessageReadError::WrongType {
+                expected: TransactionType::Message,
+                got: self.transaction_type()
+            });
+        };
+
+        let public_key = reader.public_key();
+
+        let counterpart = if &public_key == to {
+            from
+        } else if &public_key == from {
+            to
+        } else {
+            return Err(MessageReadError::NotParticipant);
+        };
+
+        let secret = reader.create_shared_secret(counterpart, salt);
+
+        read_content(format, content, &secret)
+    }
+
+    /// Read content of the `group_message` transaction.
+    ///
+    /// Message can be read by any of its recipients
+    /// and by its sender. The salt must be the same
+    /// as used to build the message.
+    pub fn read_group_message(&self, reader: &SecretKey, salt: Option<&[u8]>) -> Result<Vec<u8>, MessageReadError> {
+        let Self::GroupMessage { from, recipients, format, content } = self else {
+            return Err(MessageReadError::WrongType {
+                expected: TransactionType::GroupMessage,
+                got: self.transaction_type()
+            });
+        };
+
+        let public_key = reader.public_key();
+
+        let recipient = recipients.iter()
+            .find(|recipient| recipient.key == public_key);
+
+        // Sender can unwrap the key of any recipient
+        let (recipient, counterpart) = match recipient {
+            Some(recipient) => (recipient, from),
+
+            None if &public_key == from => match recipients.first() {
+                Some(recipient) => (recipient, &recipient.key),
+                None => return Err(MessageReadError::NotParticipant)
+            },
+
+            None => return Err(MessageReadError::NotParticipant)
+        };
+
+        if recipient.wrapped_key.len() < WRAP_NONCE_SIZE {
+            return Err(MessageReadError::KeyUnwrap);
+        }
+
+        let secret = reader.create_shared_secret(counterpart, salt);
+
+        let (nonce, wrapped_key) = recipient.wrapped_key.split_at(WRAP_NONCE_SIZE);
+
+        let content_key = XChaCha20Poly1305::new(Key::from_slice(&secret))
+            .decrypt(XNonce::from_slice(nonce), wrapped_key)
+            .map_err(|_| MessageReadError::KeyUnwrap)?;
+
+        read_content(format, content, &content_key)
+    }
+
+    /// Read content of the unencrypted
+    /// `announcement` transaction.
+    ///
+    /// Encrypted announcements can be read only
+    /// by their authors using `read_own_announcement`.
+    pub fn read_announcement(&self) -> Result<Vec<u8>, MessageReadError> {
+        let Self::Announcement { format, content, .. } = self else {
+            return Err(MessageReadError::WrongType {
+                expected: TransactionType::Announcement,
+                got: self.transaction_type()
+            });
+        };
+
+        if format.encryption != Encryption::None {
+            return Err(MessageReadError::EncryptedAnnouncement);
+        }
+
+        // Secret is not used without encryption
+        read_content(format, content, &[])
+    }
+
+    /// Read content of the `announcement` transaction
+    /// using its author's secret key.
+    ///
+    /// The salt must be the same as used
+    /// to build the announcement.
+    pub fn read_own_announcement(&self, author: &SecretKey, salt: Option<&[u8]>) -> Result<Vec<u8>, MessageReadError> {
+        let Self::Announcement { from, format, content } = self else {
+            return Err(MessageReadError::WrongType {
+                expected: TransactionType::Announcement,
+                got: self.transaction_type()
+            });
+        };
+
+        if &author.public_key() != from {
+            return Err(MessageReadError::NotParticipant);
+        }
+
+        let secret = author.create_shared_secret(from, salt);
+
+        read_content(format, content, &secret)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hyperborealib::rest_api::types::*;
+
+    use crate::block::transaction::builder::*;
+
+    use super::*;
+
+    fn encrypted() -> MessageEncoding {
+        MessageEncoding::new(
+            Encoding::Base64,
+            Encryption::ChaCha20Poly1305,
+            Compression::None
+        )
+    }
+
+    #[test]
+    fn read_message() -> Result<(), MessageReadError> {
+        let sender = SecretKey::random();
+        let receiver = SecretKey::random();
+
+        let body = MessageTransactionBuilder::new()
+            .with_receiver(receiver.public_key())
+            .with_format(encrypted())
+            .with_content(b"Hello, World!")
+            .with_encryption_salt(b"salt")
+            .build(&sender)
+            .unwrap();
+
+        // Both sender and receiver can read the message
+        assert_eq!(body.read_message(&receiver, Some(b"salt".as_slice()))?, b"Hello, World!");
+        assert_eq!(body.read_message(&sender, Some(b"salt".as_slice()))?, b"Hello, World!");
+
+        assert!(matches!(body.read_message(&receiver, Some(b"wrong salt".as_slice())), Err(MessageReadError::Decryption(_))));
+        assert!(matches!(body.read_message(&receiver, None), Err(MessageReadError::Decryption(_))));
+        assert!(matches!(body.read_message(&SecretKey::random(), Some(b"salt".as_slice())), Err(MessageReadError::NotParticipant)));
+
+        assert!(matches!(body.read_announcement(), Err(MessageReadError::WrongType { .. })));
+
+        // Broken encoding
+        let TransactionBody::Message { from, to, format, .. } = body else {
+            unreachable!();
+        };
+
+        let body = TransactionBody::Message {
+            from,
+            to,
+            format,
+            content: String::from("Hello, World!")
+        };
+
+        assert!(matches!(body.read_message(&receiver, Some(b"salt".as_slice())), Err(MessageReadError::InvalidEncoding)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn read_group_message() -> Result<(), MessageReadError> {
+        let sender = SecretKey::random();
+        let alice = SecretKey::random();
+        let bob = SecretKey::random();
+
+        let body = GroupMessageTransactionBuilder::new()
+            .with_recipients([alice.public_key(), bob.public_key()])
+            .with_format(encrypted())
+            .with_content(b"Hello, World!")
+            .with_encryption_salt(b"salt")
+            .build(&sender)
+            .unwrap();
+
+        assert_eq!(body.recipients(), vec![&alice.public_key(), &bob.public_key()]);
+
+        // All the recipients and the sender can read the message
+        for reader in [&alice, &bob, &sender] {
+            assert_eq!(body.read_group_message(reader, Some(b"salt".as_slice()))?, b"Hello, World!");
+        }
+
+        assert!(matches!(body.read_group_message(&alice, Some(b"wrong salt".as_slice())), Err(MessageReadError::KeyUnwrap)));
+        assert!(matches!(body.read_group_message(&SecretKey::random(), Some(b"salt".as_slice())), Err(MessageReadError::NotParticipant)));
+        assert!(matches!(body.read_message(&alice, Some(b"salt".as_slice())), Err(MessageReadError::WrongType { .. })));
+
+        Ok(())
+    }
+
+    #[test]
+    fn read_announcement() -> Result<(), MessageReadError> {
+        let author = SecretKey::random();
+
+        let body = AnnouncementTransactionBuilder::new()
+            .with_content(b"Hello, World!")
+            .build(&author)
+            .unwrap();
+
+        assert_eq!(body.read_announcement()?, b"Hello, World!");
+        assert_eq!(body.read_own_announcement(&author, None)?, b"Hello, World!");
+
+        // Encrypted announcement
+        let body = AnnouncementTransactionBuilder::new()
+            .with_format(encrypted())
+            .with_content(b"Hello, World!")
+            .with_encryption_salt(b"salt")
+            .build(&author)
+            .unwrap();
+
+        assert!(matches!(body.read_announcement(), Err(MessageReadError::EncryptedAnnouncement)));
+        assert!(matches!(body.read_own_announcement(&SecretKey::random(), Some(b"salt".as_slice())), Err(MessageReadError::NotParticipant)));
+        assert!(matches!(body.read_own_announcement(&author, Some(b"wrong salt".as_slice())), Err(MessageReadError::Decryption(_))));
+
+        assert_eq!(body.read_own_announcement(&author, Some(b"salt".as_slice()))?, b"Hello, World!");
+
+        Ok(())
+    }
+}
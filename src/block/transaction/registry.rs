@@ -0,0 +1,486 @@
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use super::*;
+
+#[derive(Debug, thiserror::Error)]
+pub enum TransactionTypeError {
+    #[error(transparent)]
+    Json(#[from] AsJsonError),
+
+    #[error("Transaction body is not of a custom type")]
+    NotCustom,
+
+    #[error("Expected custom transaction type '{expected}', got '{got}'")]
+    TypeMismatch {
+        expected: String,
+        got: String
+    },
+
+    #[error("Unknown custom transaction type: {0}")]
+    UnknownType(String),
+
+    #[error("Invalid payload of the custom transaction type '{type_id}': {reason}")]
+    InvalidPayload {
+        type_id: String,
+        reason: String
+    }
+}
+
+/// Handler of the custom transactions' payloads
+/// of a single type.
+///
+/// Most applications should implement the typed
+/// `CustomTransaction` trait instead.
+pub trait TransactionTypeHandler: std::fmt::Debug + Send + Sync {
+    /// Identifier of the handled transaction type.
+    fn type_id(&self) -> &str;
+
+    /// Verify transaction's payload, returning
+    /// the reason if it's invalid.
+    fn check(&self, author: &PublicKey, payload: &[u8]) -> Result<(), String>;
+
+    /// Convert transaction's payload to JSON.
+    fn payload_to_json(&self, payload: &[u8]) -> Result<Json, String>;
+
+    /// Convert JSON to the transaction's payload.
+    fn payload_from_json(&self, json: &Json) -> Result<Vec<u8>, String>;
+}
+
+/// Typed application-defined transaction.
+///
+/// ```
+/// use serde_json::{json, Value as Json};
+///
+/// use hyperborealib::crypto::asymmetric::PublicKey;
+/// use hyperborealib::rest_api::{AsJson, AsJsonError};
+///
+/// use hyperchain::prelude::*;
+///
+/// struct Vote {
+///     proposal: u64
+/// }
+///
+/// impl AsJson for Vote {
+///     fn to_json(&self) -> Result<Json, AsJsonError> {
+///         Ok(json!({ "proposal": self.proposal }))
+///     }
+///
+///     fn from_json(json: &Json) -> Result<Self, AsJsonError> {
+///         Ok(Self {
+///             proposal: json.get("proposal")
+///                 .and_then(Json::as_u64)
+///                 .ok_or_else(|| AsJsonError::FieldValueInvalid("proposal"))?
+///         })
+///     }
+/// }
+///
+/// impl CustomTransaction for Vote {
+///     const TYPE_ID: &'static str = "vote";
+///
+///     fn encode(&self) -> Vec<u8> {
+///         self.proposal.to_be_bytes().to_vec()
+///     }
+///
+///     fn decode(payload: &[u8]) -> Result<Self, String> {
+///         let proposal = payload.try_into()
+///             .map_err(|_| String::from("invalid payload length"))?;
+///
+///         Ok(Self {
+///             proposal: u64::from_be_bytes(proposal)
+///         })
+///     }
+/// }
+///
+/// let registry = TransactionTypeRegistry::default()
+///     .with_type::<Vote>();
+///
+/// let body = Vote { proposal: 1 }.to_body();
+///
+/// assert_eq!(registry.decode::<Vote>(&body).unwrap().proposal, 1);
+/// ```
+pub trait CustomTransaction: AsJson + Sized + Send + Sync + 'static {
+    /// Identifier of the transaction type.
+    const TYPE_ID: &'static str;
+
+    /// Encode transaction to the payload bytes.
+    fn encode(&self) -> Vec<u8>;
+
+    /// Decode transaction from the payload bytes.
+    fn decode(payload: &[u8]) -> Result<Self, String>;
+
+    #[inline]
+    /// Verify transaction's semantic rules,
+    /// returning the reason if it's invalid.
+    fn validate(&self, _author: &PublicKey) -> Result<(), String> {
+        Ok(())
+    }
+
+    #[inline]
+    /// Make transaction's body from the transaction.
+    fn to_body(&self) -> TransactionBody {
+        TransactionBody::Custom {
+            type_id: Self::TYPE_ID.to_string(),
+            payload: self.encode()
+        }
+    }
+}
+
+/// Adapter of the `CustomTransaction` types
+/// to the `TransactionTypeHandler` trait.
+struct CustomTransactionHandler<T>(PhantomData<fn() -> T>);
+
+impl<T: CustomTransaction> std::fmt::Debug for CustomTransactionHandler<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("CustomTransactionHandler")
+            .field(&T::TYPE_ID)
+            .finish()
+    }
+}
+
+impl<T: CustomTransaction> TransactionTypeHandler for CustomTransactionHandler<T> {
+    #[inline]
+    fn type_id(&self) -> &str {
+        T::TYPE_ID
+    }
+
+    fn check(&self, author: &PublicKey, payload: &[u8]) -> Result<(), String> {
+        T::decode(payload)?.validate(author)
+    }
+
+    fn payload_to_json(&self, payload: &[u8]) -> Result<Json, String> {
+        T::decode(payload)?
+            .to_json()
+            .map_err(|err| err.to_string())
+    }
+
+    fn payload_from_json(&self, json: &Json) -> Result<Vec<u8>, String> {
+        T::from_json(json)
+            .map(|transaction| transaction.encode())
+            .map_err(|err| err.to_string())
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+/// Registry of the custom transaction types.
+///
+/// Maps type identifiers to the handlers used to
+/// validate custom transactions' payloads and to
+/// convert them to and from JSON.
+pub struct TransactionTypeRegistry {
+    /// Reject custom transactions of the
+    /// unregistered types.
+    ///
+    /// Default is false.
+    pub reject_unknown: bool,
+
+    types: HashMap<String, Arc<dyn TransactionTypeHandler>>
+}
+
+impl TransactionTypeRegistry {
+    #[inline]
+    /// Register typed custom transaction.
+    pub fn with_type<T: CustomTransaction>(self) -> Self {
+        self.with_handler(CustomTransactionHandler::<T>(PhantomData))
+    }
+
+    #[inline]
+    /// Register custom transaction type handler.
+    ///
+    /// Previously registered handler of
+    /// the same type is replaced.
+    pub fn with_handler(mut self, handler: impl TransactionTypeHandler + 'static) -> Self {
+        self.types.insert(handler.type_id().to_string(), Arc::new(handler));
+
+        self
+    }
+
+    #[inline]
+    /// Change whether custom transactions of
+    /// the unregistered types are rejected.
+    pub fn with_reject_unknown(mut self, reject_unknown: bool) -> Self {
+        self.reject_unknown = reject_unknown;
+
+        self
+    }
+
+    #[inline]
+    /// Get handler of the custom transaction type.
+    pub fn get(&self, type_id: impl AsRef<str>) -> Option<&Arc<dyn TransactionTypeHandler>> {
+        self.types.get(type_id.as_ref())
+    }
+
+    #[inline]
+    /// Check if custom transaction type is registered.
+    pub fn is_registered(&self, type_id: impl AsRef<str>) -> bool {
+        self.types.contains_key(type_id.as_ref())
+    }
+
+    #[inline]
+    /// Iterate over registered type identifiers.
+    pub fn types(&self) -> impl Iterator<Item = &str> {
+        self.types.keys().map(String::as_str)
+    }
+
+    /// Verify custom transaction's payload.
+    ///
+    /// Built-in transaction types are always accepted.
+    pub fn check(&self, author: &PublicKey, body: &TransactionBody) -> Option<TransactionValidationResult> {
+        let TransactionBody::Custom { type_id, payload } = body else {
+            return None;
+        };
+
+        match self.types.get(type_id) {
+            Some(handler) => handler.check(author, payload).err()
+                .map(|reason| TransactionValidationResult::InvalidCustomPayload {
+                    type_id: type_id.clone(),
+                    reason
+                }),
+
+            None if self.reject_unknown => Some(TransactionValidationResult::UnknownTransactionType {
+                type_id: type_id.clone()
+            }),
+
+            None => None
+        }
+    }
+
+    /// Decode custom transaction of a registered type.
+    pub fn decode<T: CustomTransaction>(&self, body: &TransactionBody) -> Result<T, TransactionTypeError> {
+        let TransactionBody::Custom { type_id, payload } = body else {
+            return Err(TransactionTypeError::NotCustom);
+        };
+
+        if type_id != T::TYPE_ID {
+            return Err(TransactionTypeError::TypeMismatch {
+                expected: T::TYPE_ID.to_string(),
+                got: type_id.clone()
+            });
+        }
+
+        if !self.is_registered(type_id) {
+            return Err(TransactionTypeError::UnknownType(type_id.clone()));
+        }
+
+        T::decode(payload).map_err(|reason| TransactionTypeError::InvalidPayload {
+            type_id: type_id.clone(),
+            reason
+        })
+    }
+
+    /// Convert transaction's body to JSON.
+    ///
+    /// Payloads of the registered custom types
+    /// are converted by their handlers. Other
+    /// bodies are converted using `AsJson`.
+    pub fn body_to_json(&self, body: &TransactionBody) -> Result<Json, TransactionTypeError> {
+        let TransactionBody::Custom { type_id, payload } = body else {
+            return Ok(body.to_json()?);
+        };
+
+        let Some(handler) = self.types.get(type_id) else {
+            return Ok(body.to_json()?);
+        };
+
+        let payload = handler.payload_to_json(payload)
+            .map_err(|reason| TransactionTypeError::InvalidPayload {
+                type_id: type_id.clone(),
+                reason
+            })?;
+
+        Ok(json!({
+            "type": body.transaction_type().to_string(),
+            "body": payload
+        }))
+    }
+
+    /// Convert JSON made by `body_to_json`
+    /// to the transaction's body.
+    pub fn body_from_json(&self, json: &Json) -> Result<TransactionBody, TransactionTypeError> {
+        let Some(transaction_type) = json.get("type").and_then(Json::as_str) else {
+            return Err(AsJsonError::FieldNotFound("type").into());
+        };
+
+        let Ok(TransactionType::Custom(type_id)) = TransactionType::from_str(transaction_type) else {
+            return Ok(TransactionBody::from_json(json)?);
+        };
+
+        let Some(handler) = self.types.get(&type_id) else {
+            return Ok(TransactionBody::from_json(json)?);
+        };
+
+        let Some(payload) = json.get("body") else {
+            return Err(AsJsonError::FieldNotFound("body").into());
+        };
+
+        let payload = handler.payload_from_json(payload)
+            .map_err(|reason| TransactionTypeError::InvalidPayload {
+                type_id: type_id.clone(),
+                reason
+            })?;
+
+        Ok(TransactionBody::Custom {
+            type_id,
+            payload
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hyperborealib::crypto::asymmetric::SecretKey;
+
+    use crate::block::transaction::builder::TransactionBuilder;
+
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    struct Vote {
+        proposal: u64,
+        approve: bool
+    }
+
+    impl AsJson for Vote {
+        fn to_json(&self) -> Result<Json, AsJsonError> {
+            Ok(json!({
+                "proposal": self.proposal,
+                "approve": self.approve
+            }))
+        }
+
+        fn from_json(json: &Json) -> Result<Self, AsJsonError> where Self: Sized {
+            Ok(Self {
+                proposal: json.get("proposal")
+                    .and_then(Json::as_u64)
+                    .ok_or_else(|| AsJsonError::FieldValueInvalid("proposal"))?,
+
+                approve: json.get("approve")
+                    .and_then(Json::as_bool)
+                    .ok_or_else(|| AsJsonError::FieldValueInvalid("approve"))?
+            })
+        }
+    }
+
+    impl CustomTransaction for Vote {
+        const TYPE_ID: &'static str = "vote";
+
+        fn encode(&self) -> Vec<u8> {
+            let mut payload = self.proposal.to_be_bytes().to_vec();
+
+            payload.push(self.approve as u8);
+
+            payload
+        }
+
+        fn decode(payload: &[u8]) -> Result<Self, String> {
+            if payload.len() != 9 {
+                return Err(String::from("invalid payload length"));
+            }
+
+            let mut proposal = [0; 8];
+
+            proposal.copy_from_slice(&payload[..8]);
+
+            Ok(Self {
+                proposal: u64::from_be_bytes(proposal),
+                approve: payload[8] == 1
+            })
+        }
+
+        fn validate(&self, _author: &PublicKey) -> Result<(), String> {
+            if self.proposal == 0 {
+                return Err(String::from("proposal 0 doesn't exist"));
+            }
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn registry() -> Result<(), TransactionTypeError> {
+        let author = SecretKey::random().public_key();
+
+        let registry = TransactionTypeRegistry::default()
+            .with_type::<Vote>();
+
+        let vote = Vote {
+            proposal: 1,
+            approve: true
+        };
+
+        let body = vote.to_body();
+
+        assert!(registry.is_registered("vote"));
+        assert!(registry.check(&author, &body).is_none());
+        assert_eq!(registry.decode::<Vote>(&body)?, vote);
+
+        // Typed JSON representation
+        let json = registry.body_to_json(&body)?;
+
+        assert_eq!(json["type"], "custom:vote");
+        assert_eq!(json["body"]["proposal"], 1);
+        assert_eq!(registry.body_from_json(&json)?, body);
+
+        // Invalid payloads
+        let invalid = Vote { proposal: 0, approve: true }.to_body();
+
+        assert_eq!(registry.check(&author, &invalid), Some(TransactionValidationResult::InvalidCustomPayload {
+            type_id: String::from("vote"),
+            reason: String::from("proposal 0 doesn't exist")
+        }));
+
+        let undecodable = TransactionBody::Custom {
+            type_id: String::from("vote"),
+            payload: b"Hello, World!".to_vec()
+        };
+
+        assert!(registry.check(&author, &undecodable).is_some());
+        assert!(matches!(registry.decode::<Vote>(&undecodable), Err(TransactionTypeError::InvalidPayload { .. })));
+
+        // Unknown types
+        let unknown = TransactionBody::Custom {
+            type_id: String::from("transfer"),
+            payload: b"Hello, World!".to_vec()
+        };
+
+        assert!(registry.check(&author, &unknown).is_none());
+        assert_eq!(registry.body_from_json(&registry.body_to_json(&unknown)?)?, unknown);
+
+        let registry = registry.with_reject_unknown(true);
+
+        assert_eq!(registry.check(&author, &unknown), Some(TransactionValidationResult::UnknownTransactionType {
+            type_id: String::from("transfer")
+        }));
+
+        Ok(())
+    }
+
+    #[test]
+    fn validate() -> Result<(), TransactionValidationError> {
+        let context = ValidationContext::default()
+            .with_transaction_types(TransactionTypeRegistry::default().with_type::<Vote>());
+
+        let transaction = TransactionBuilder::new()
+            .with_body(Vote { proposal: 1, approve: false }.to_body())
+            .sign(&SecretKey::random())
+            .unwrap();
+
+        assert_eq!(Transaction::from_json(&transaction.to_json().unwrap()).unwrap(), transaction);
+
+        assert!(transaction.validate()?.is_valid());
+        assert!(transaction.validate_with(&context)?.is_valid());
+
+        let transaction = TransactionBuilder::new()
+            .with_body(Vote { proposal: 0, approve: false }.to_body())
+            .sign(&SecretKey::random())
+            .unwrap();
+
+        assert!(transaction.validate()?.is_valid());
+        assert!(!transaction.validate_with(&context)?.is_valid());
+        assert!(!transaction.validate_report_with(&context).is_valid());
+
+        Ok(())
+    }
+}
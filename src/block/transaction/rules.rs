@@ -0,0 +1,278 @@
+use std::sync::Arc;
+
+use hyperborealib::crypto::asymmetric::PublicKey;
+
+use super::*;
+
+/// Custom rule of transactions' bodies validation.
+///
+/// ```
+/// use hyperborealib::crypto::asymmetric::PublicKey;
+/// use hyperchain::prelude::*;
+///
+/// #[derive(Debug)]
+/// struct NoRawTransactions;
+///
+/// impl TransactionBodyRule for NoRawTransactions {
+///     fn name(&self) -> &str {
+///         "no-raw-transactions"
+///     }
+///
+///     fn check(&self, _author: &PublicKey, body: &TransactionBody) -> Result<(), String> {
+///         match body {
+///             TransactionBody::Raw(_) => Err(String::from("raw transactions are not allowed")),
+///             _ => Ok(())
+///         }
+///     }
+/// }
+///
+/// let rules = TransactionBodyRules::default()
+///     .with_rule(NoRawTransactions);
+/// ```
+pub trait TransactionBodyRule: std::fmt::Debug + Send + Sync {
+    /// Name of the rule.
+    fn name(&self) -> &str;
+
+    /// Verify transaction's body, returning
+    /// the reason if the rule is violated.
+    fn check(&self, author: &PublicKey, body: &TransactionBody) -> Result<(), String>;
+}
+
+#[derive(Debug, Clone)]
+/// Semantic rules of transactions' bodies.
+///
+/// Built-in rules verify that:
+///
+/// 1. Messages and announcements are sent
+///    by the transaction's author.
+///
+/// 2. Messages and announcements content
+///    is not empty and can be decoded
+///    from its text encoding.
+///
+/// 3. Transaction's content is not larger
+///    than the allowed maximum.
+///
+/// 4. Messages have a receiver other than their
+///    sender and group messages have at least
+///    one recipient.
+///
+/// 5. Custom transactions' type identifiers
+///    are well-formed.
+pub struct TransactionBodyRules {
+    /// Maximal size of the transaction's content
+    /// in bytes.
+    ///
+    /// Default is 1 MiB.
+    pub max_content_size: usize,
+
+    /// Custom rules.
+    rules: Vec<Arc<dyn TransactionBodyRule>>
+}
+
+impl Default for TransactionBodyRules {
+    fn default() -> Self {
+        Self {
+            max_content_size: Self::DEFAULT_MAX_CONTENT_SIZE,
+            rules: Vec::new()
+        }
+    }
+}
+
+impl TransactionBodyRules {
+    /// Default maximal size of the transaction's content.
+    pub const DEFAULT_MAX_CONTENT_SIZE: usize = 1024 * 1024;
+
+    #[inline]
+    /// Change maximal size of the transaction's content.
+    pub fn with_max_content_size(mut self, max_content_size: usize) -> Self {
+        self.max_content_size = max_content_size;
+
+        self
+    }
+
+    #[inline]
+    /// Add custom rule.
+    pub fn with_rule(mut self, rule: impl TransactionBodyRule + 'static) -> Self {
+        self.rules.push(Arc::new(rule));
+
+        self
+    }
+
+    #[inline]
+    /// Get list of custom rules.
+    pub fn rules(&self) -> &[Arc<dyn TransactionBodyRule>] {
+        &self.rules
+    }
+
+    #[inline]
+    /// Verify transaction's body, returning
+    /// the first violated rule.
+    pub fn check(&self, author: &PublicKey, body: &TransactionBody) -> Option<TransactionValidationResult> {
+        self.check_all(author, body).into_iter().next()
+    }
+
+    /// Verify transaction's body, returning
+    /// all the violated rules.
+    pub fn check_all(&self, author: &PublicKey, body: &TransactionBody) -> Vec<TransactionValidationResult> {
+        let mut results = Vec::new();
+
+        match body {
+            TransactionBody::Raw(bytes) |
+            TransactionBody::Custom { payload: bytes, .. } => {
+                // Validate custom body's type
+                if let TransactionBody::Custom { type_id, .. } = body {
+                    if !TransactionType::is_valid_custom_id(type_id) {
+                        results.push(TransactionValidationResult::InvalidTransactionType {
+                            type_id: type_id.clone()
+                        });
+                    }
+                }
+
+                // Validate body's size
+                if bytes.len() > self.max_content_size {
+                    results.push(TransactionValidationResult::ContentTooLarge {
+                        size: bytes.len(),
+                        max_size: self.max_content_size
+                    });
+                }
+            }
+
+            TransactionBody::Message { from, format, content, .. } |
+            TransactionBody::Announcement { from, format, content } |
+            TransactionBody::GroupMessage { from, format, content, .. } => {
+                // Validate body's sender
+                if from != author {
+                    results.push(TransactionValidationResult::InvalidSender {
+                        author: author.clone(),
+                        from: from.clone()
+                    });
+                }
+
+                // Validate body's content
+                if content.is_empty() {
+                    results.push(TransactionValidationResult::EmptyContent);
+                } else if content.len() > self.max_content_size {
+                    results.push(TransactionValidationResult::ContentTooLarge {
+                        size: content.len(),
+                        max_size: self.max_content_size
+                    });
+                } else if format.encoding.decode(content).is_err() {
+                    results.push(TransactionValidationResult::UndecodableContent);
+                }
+
+                // Validate message's receivers
+                match body {
+                    TransactionBody::Message { from, to, .. } if from == to => {
+                        results.push(TransactionValidationResult::NoReceiver);
+                    }
+
+                    TransactionBody::GroupMessage { recipients, .. } if recipients.is_empty() => {
+                        results.push(TransactionValidationResult::NoRecipients);
+                    }
+
+                    _ => ()
+                }
+            }
+        }
+
+        // Validate custom rules
+        for rule in &self.rules {
+            if let Err(reason) = rule.check(author, body) {
+                results.push(TransactionValidationResult::RuleViolation {
+                    rule: rule.name().to_string(),
+                    reason
+                });
+            }
+        }
+
+        results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hyperborealib::crypto::asymmetric::SecretKey;
+
+    use crate::block::transaction::builder::message::tests::get_body;
+
+    use super::*;
+
+    #[derive(Debug)]
+    struct NoRawTransactions;
+
+    impl TransactionBodyRule for NoRawTransactions {
+        fn name(&self) -> &str {
+            "no-raw-transactions"
+        }
+
+        fn check(&self, _author: &PublicKey, body: &TransactionBody) -> Result<(), String> {
+            match body {
+                TransactionBody::Raw(_) => Err(String::from("raw transactions are not allowed")),
+                _ => Ok(())
+            }
+        }
+    }
+
+    #[test]
+    fn check() {
+        let (body, secret) = get_body();
+
+        let rules = TransactionBodyRules::default();
+
+        assert!(rules.check(&secret.public_key(), &body).is_none());
+
+        // Message sent by someone else
+        let author = SecretKey::random().public_key();
+
+        assert_eq!(rules.check(&author, &body), Some(TransactionValidationResult::InvalidSender {
+            author: author.clone(),
+            from: secret.public_key()
+        }));
+
+        // Message sent to its sender
+        let TransactionBody::Message { from, format, content, .. } = body else {
+            unreachable!();
+        };
+
+        let body = TransactionBody::Message {
+            to: from.clone(),
+            from,
+            format,
+            content
+        };
+
+        assert_eq!(rules.check_all(&secret.public_key(), &body), vec![
+            TransactionValidationResult::NoReceiver
+        ]);
+
+        // Too large raw transaction
+        let rules = rules.with_max_content_size(4)
+            .with_rule(NoRawTransactions);
+
+        assert_eq!(rules.check_all(&author, &TransactionBody::Raw(b"Hello, World!".to_vec())), vec![
+            TransactionValidationResult::ContentTooLarge {
+                size: 13,
+                max_size: 4
+            },
+            TransactionValidationResult::RuleViolation {
+                rule: String::from("no-raw-transactions"),
+                reason: String::from("raw transactions are not allowed")
+            }
+        ]);
+
+        // Malformed custom transaction type
+        for type_id in ["", "app transfer"] {
+            let body = TransactionBody::Custom {
+                type_id: String::from(type_id),
+                payload: vec![]
+            };
+
+            assert_eq!(rules.check_all(&author, &body), vec![
+                TransactionValidationResult::InvalidTransactionType {
+                    type_id: String::from(type_id)
+                }
+            ]);
+        }
+    }
+}
@@ -13,9 +13,32 @@ use hyperborealib::rest_api::{
 };
 
 use crate::block::hash::Hash;
+use crate::block::format::{EncodingFormat, FieldsHasher};
 
 use super::TransactionType;
 
+#[derive(Debug, Clone, PartialEq, Eq, std::hash::Hash, Serialize, Deserialize)]
+/// Recipient of the group message.
+pub struct GroupRecipient {
+    pub(crate) key: PublicKey,
+    pub(crate) wrapped_key: Vec<u8>
+}
+
+impl GroupRecipient {
+    #[inline]
+    /// Public key of the recipient.
+    pub fn key(&self) -> &PublicKey {
+        &self.key
+    }
+
+    #[inline]
+    /// Message's content key encrypted with the
+    /// shared secret of the sender and the recipient.
+    pub fn wrapped_key(&self) -> &[u8] {
+        &self.wrapped_key
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, std::hash::Hash, Serialize, Deserialize)]
 pub enum TransactionBody {
     Raw(Vec<u8>),
@@ -31,20 +54,118 @@ pub enum TransactionBody {
         from: PublicKey,
         format: MessageEncoding,
         content: String
+    },
+
+    /// Message sent to multiple recipients.
+    ///
+    /// Content is encoded once using a random content
+    /// key which is wrapped for each recipient.
+    GroupMessage {
+        from: PublicKey,
+        recipients: Vec<GroupRecipient>,
+        format: MessageEncoding,
+        content: String
+    },
+
+    /// Application-defined transaction.
+    ///
+    /// Payload is an opaque bytes sequence which can
+    /// be decoded and validated using the types
+    /// registered in the `TransactionTypeRegistry`.
+    Custom {
+        type_id: String,
+        payload: Vec<u8>
     }
 }
 
 impl TransactionBody {
+    /// Prefix of the hashed data of
+    /// transactions' bodies.
+    pub const HASH_DOMAIN: &'static [u8] = b"hyperchain/transaction/body";
+
     #[inline]
     pub fn transaction_type(&self) -> TransactionType {
         TransactionType::from(self)
     }
 
-    /// Calculate hash of the transaction body.
+    /// Get public keys of the body's recipients.
+    ///
+    /// Only messages and group messages have recipients.
+    pub fn recipients(&self) -> Vec<&PublicKey> {
+        match self {
+            Self::Message { to, .. } => vec![to],
+
+            Self::GroupMessage { recipients, .. } => recipients.iter()
+                .map(GroupRecipient::key)
+                .collect(),
+
+            _ => vec![]
+        }
+    }
+
+    /// Get size of the body's content in bytes.
+    ///
+    /// Public keys and content are counted in their
+    /// raw form, without text encoding overhead.
+    pub fn size(&self) -> usize {
+        match self {
+            Self::Raw(bytes) => bytes.len(),
+
+            Self::Message { from, to, content, .. } => {
+                from.to_bytes().len() + to.to_bytes().len() + content.len()
+            }
+
+            Self::Announcement { from, content, .. } => {
+                from.to_bytes().len() + content.len()
+            }
+
+            Self::GroupMessage { from, recipients, content, .. } => {
+                let recipients = recipients.iter()
+                    .map(|recipient| recipient.key.to_bytes().len() + recipient.wrapped_key.len())
+                    .sum::<usize>();
+
+                from.to_bytes().len() + recipients + content.len()
+            }
+
+            Self::Custom { type_id, payload } => type_id.len() + payload.len()
+        }
+    }
+
+    #[inline]
+    /// Check if the body can be hashed unambiguously
+    /// using given encoding format.
+    ///
+    /// V1 format doesn't commit to the body's type,
+    /// so group messages and custom transactions
+    /// could share their hash with a raw body
+    /// of the same bytes.
+    pub fn is_supported_by(&self, format: EncodingFormat) -> bool {
+        format != EncodingFormat::V1 || !matches!(self, Self::GroupMessage { .. } | Self::Custom { .. })
+    }
+
+    #[inline]
+    /// Calculate hash of the transaction body
+    /// using the latest encoding format.
     ///
     /// This is a relatively heavy function and
     /// it should not be called often.
     pub fn hash(&self) -> Hash {
+        self.hash_with_format(EncodingFormat::LATEST)
+    }
+
+    /// Calculate hash of the transaction body
+    /// using given encoding format.
+    ///
+    /// This is a relatively heavy function and
+    /// it should not be called often.
+    pub fn hash_with_format(&self, format: EncodingFormat) -> Hash {
+        match format {
+            EncodingFormat::V1 => self.hash_v1(),
+            EncodingFormat::V2 => self.hash_v2()
+        }
+    }
+
+    fn hash_v1(&self) -> Hash {
         let mut hasher = blake3::Hasher::new();
 
         match self {
@@ -64,10 +185,79 @@ impl TransactionBody {
                 hasher.update(format.to_string().as_bytes());
                 hasher.update(content.as_bytes());
             }
+
+            // Group messages and custom bodies can share their
+            // hash with raw ones so they're rejected in this
+            // format, see `is_supported_by`
+            Self::GroupMessage { from, recipients, format, content } => {
+                hasher.update(&from.to_bytes());
+                hasher.update(&(recipients.len() as u64).to_be_bytes());
+
+                for recipient in recipients {
+                    hasher.update(&recipient.key.to_bytes());
+                    hasher.update(&(recipient.wrapped_key.len() as u64).to_be_bytes());
+                    hasher.update(&recipient.wrapped_key);
+                }
+
+                hasher.update(format.to_string().as_bytes());
+                hasher.update(content.as_bytes());
+            }
+
+            Self::Custom { type_id, payload } => {
+                hasher.update(&(type_id.len() as u64).to_be_bytes());
+                hasher.update(type_id.as_bytes());
+                hasher.update(payload);
+            }
         }
 
         hasher.finalize().into()
     }
+
+    fn hash_v2(&self) -> Hash {
+        let mut hasher = FieldsHasher::new(Self::HASH_DOMAIN, EncodingFormat::V2);
+
+        hasher.field(self.transaction_type().to_string());
+
+        match self {
+            Self::Raw(bytes) => {
+                hasher.field(bytes);
+            }
+
+            Self::Message { from, to, format, content } => {
+                hasher.field(from.to_bytes())
+                    .field(to.to_bytes())
+                    .field(format.to_string())
+                    .field(content);
+            }
+
+            Self::Announcement { from, format, content } => {
+                hasher.field(from.to_bytes())
+                    .field(format.to_string())
+                    .field(content);
+            }
+
+            Self::GroupMessage { from, recipients, format, content } => {
+                hasher.field(from.to_bytes())
+                    .field((recipients.len() as u64).to_be_bytes());
+
+                for recipient in recipients {
+                    hasher.field(recipient.key.to_bytes())
+                        .field(&recipient.wrapped_key);
+                }
+
+                hasher.field(format.to_string())
+                    .field(content);
+            }
+
+            // Type ID is already hashed as a part
+            // of the transaction type
+            Self::Custom { payload, .. } => {
+                hasher.field(payload);
+            }
+        }
+
+        hasher.finalize()
+    }
 }
 
 impl AsJson for TransactionBody {
@@ -91,6 +281,24 @@ impl AsJson for TransactionBody {
                     "content": content
                 })
             }
+
+            Self::GroupMessage { from, recipients, format, content } => {
+                let recipients = recipients.iter()
+                    .map(|recipient| json!({
+                        "key": recipient.key.to_base64(),
+                        "wrapped_key": base64::encode(&recipient.wrapped_key)
+                    }))
+                    .collect::<Vec<_>>();
+
+                json!({
+                    "from": from.to_base64(),
+                    "recipients": recipients,
+                    "format": format.to_string(),
+                    "content": content
+                })
+            }
+
+            Self::Custom { payload, .. } => json!(base64::encode(payload))
         };
 
         Ok(json!({
@@ -162,6 +370,57 @@ impl AsJson for TransactionBody {
                 })
             }
 
+            Ok(TransactionType::GroupMessage) => {
+                let Some(recipients) = transaction_body.get("recipients").and_then(Json::as_array) else {
+                    return Err(AsJsonError::FieldValueInvalid("body.recipients"));
+                };
+
+                Ok(Self::GroupMessage {
+                    from: transaction_body.get("from")
+                        .and_then(Json::as_str)
+                        .map(PublicKey::from_base64)
+                        .ok_or_else(|| AsJsonError::FieldValueInvalid("body.from"))??,
+
+                    recipients: recipients.iter()
+                        .map(|recipient| {
+                            Ok::<_, AsJsonError>(GroupRecipient {
+                                key: recipient.get("key")
+                                    .and_then(Json::as_str)
+                                    .map(PublicKey::from_base64)
+                                    .ok_or_else(|| AsJsonError::FieldValueInvalid("body.recipients.key"))??,
+
+                                wrapped_key: recipient.get("wrapped_key")
+                                    .and_then(Json::as_str)
+                                    .map(base64::decode)
+                                    .ok_or_else(|| AsJsonError::FieldValueInvalid("body.recipients.wrapped_key"))??
+                            })
+                        })
+                        .collect::<Result<Vec<_>, _>>()?,
+
+                    format: transaction_body.get("format")
+                        .and_then(Json::as_str)
+                        .map(MessageEncoding::from_str)
+                        .ok_or_else(|| AsJsonError::FieldValueInvalid("body.format"))?
+                        .map_err(|err| AsJsonError::Other(err.into()))?,
+
+                    content: transaction_body.get("content")
+                        .and_then(Json::as_str)
+                        .map(String::from)
+                        .ok_or_else(|| AsJsonError::FieldValueInvalid("body.content"))?
+                })
+            }
+
+            Ok(TransactionType::Custom(type_id)) => {
+                let payload = transaction_body.as_str()
+                    .map(base64::decode)
+                    .ok_or_else(|| AsJsonError::FieldValueInvalid("body"))??;
+
+                Ok(Self::Custom {
+                    type_id,
+                    payload
+                })
+            }
+
             Err(()) => Err(AsJsonError::FieldValueInvalid("type"))
         }
     }
@@ -171,6 +430,7 @@ impl AsJson for TransactionBody {
 pub(crate) mod tests {
     use crate::block::transaction::builder::message::tests::get_body as get_message;
     use crate::block::transaction::builder::announcement::tests::get_body as get_announcement;
+    use crate::block::transaction::builder::group_message::tests::get_body as get_group_message;
 
     use super::*;
 
@@ -179,8 +439,14 @@ pub(crate) mod tests {
         let transactions = [
             TransactionBody::Raw(b"Hello, World!".to_vec()),
 
+            TransactionBody::Custom {
+                type_id: String::from("vote"),
+                payload: b"Hello, World!".to_vec()
+            },
+
             get_message().0,
-            get_announcement().0
+            get_announcement().0,
+            get_group_message().0
         ];
 
         for transaction in transactions {
@@ -1,20 +1,50 @@
-use serde::{Serialize, Deserialize};
+use serde::{Serialize, Serializer, Deserialize, Deserializer};
 
 use super::TransactionBody;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+/// Type of the transaction's body.
+///
+/// Serialized as a string in the same
+/// format as its `Display` implementation.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum TransactionType {
     Raw,
     Message,
-    Announcement
+    Announcement,
+    GroupMessage,
+
+    /// Application-defined transaction type.
+    ///
+    /// Serialized as `custom:<type_id>`.
+    Custom(String)
+}
+
+impl TransactionType {
+    /// Prefix of the custom transaction types.
+    pub const CUSTOM_PREFIX: &'static str = "custom:";
+
+    /// Maximal length of the custom transaction
+    /// type's identifier in bytes.
+    pub const MAX_CUSTOM_ID_LENGTH: usize = 64;
+
+    /// Check that the custom transaction type's identifier
+    /// is not empty, not longer than the allowed maximum
+    /// and consists of printable ASCII characters only.
+    pub fn is_valid_custom_id(type_id: &str) -> bool {
+        !type_id.is_empty()
+            && type_id.len() <= Self::MAX_CUSTOM_ID_LENGTH
+            && type_id.bytes().all(|byte| byte.is_ascii_graphic())
+    }
 }
 
 impl std::fmt::Display for TransactionType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::Raw          => write!(f, "raw"),
-            Self::Message      => write!(f, "message"),
-            Self::Announcement => write!(f, "announcement")
+            Self::Raw             => write!(f, "raw"),
+            Self::Message         => write!(f, "message"),
+            Self::Announcement    => write!(f, "announcement"),
+            Self::GroupMessage    => write!(f, "group_message"),
+            Self::Custom(type_id) => write!(f, "{}{type_id}", Self::CUSTOM_PREFIX)
         }
     }
 }
@@ -24,21 +54,89 @@ impl std::str::FromStr for TransactionType {
 
     fn from_str(str: &str) -> Result<Self, Self::Err> {
         match str {
-            "raw"          => Ok(Self::Raw),
-            "message"      => Ok(Self::Message),
-            "announcement" => Ok(Self::Announcement),
+            "raw"           => Ok(Self::Raw),
+            "message"       => Ok(Self::Message),
+            "announcement"  => Ok(Self::Announcement),
+            "group_message" => Ok(Self::GroupMessage),
 
-            _ => Err(())
+            _ => match str.strip_prefix(Self::CUSTOM_PREFIX) {
+                Some(type_id) if Self::is_valid_custom_id(type_id) => Ok(Self::Custom(type_id.to_string())),
+
+                _ => Err(())
+            }
         }
     }
 }
 
+impl Serialize for TransactionType {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for TransactionType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+
+        value.parse().map_err(|_| {
+            serde::de::Error::custom(format!("invalid transaction type: {value}"))
+        })
+    }
+}
+
 impl From<&TransactionBody> for TransactionType {
     fn from(value: &TransactionBody) -> Self {
         match value {
             TransactionBody::Raw { .. }          => Self::Raw,
             TransactionBody::Message { .. }      => Self::Message,
-            TransactionBody::Announcement { .. } => Self::Announcement
+            TransactionBody::Announcement { .. } => Self::Announcement,
+            TransactionBody::GroupMessage { .. } => Self::GroupMessage,
+
+            TransactionBody::Custom { type_id, .. } => Self::Custom(type_id.clone())
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let types = [
+            TransactionType::Raw,
+            TransactionType::Message,
+            TransactionType::Announcement,
+            TransactionType::GroupMessage,
+            TransactionType::Custom(String::from("vote")),
+            TransactionType::Custom(String::from("app:transfer"))
+        ];
+
+        for transaction_type in types {
+            assert_eq!(TransactionType::from_str(&transaction_type.to_string()), Ok(transaction_type));
+        }
+
+        assert_eq!(TransactionType::from_str("custom:"), Err(()));
+        assert_eq!(TransactionType::from_str("custom:app transfer"), Err(()));
+        assert_eq!(TransactionType::from_str(&format!("custom:{}", "a".repeat(65))), Err(()));
+        assert_eq!(TransactionType::from_str("unknown"), Err(()));
+    }
+
+    #[test]
+    fn serialize() -> Result<(), serde_json::Error> {
+        let custom = TransactionType::Custom(String::from("vote"));
+
+        assert_eq!(serde_json::to_string(&TransactionType::Raw)?, "\"raw\"");
+        assert_eq!(serde_json::to_string(&custom)?, "\"custom:vote\"");
+
+        assert_eq!(serde_json::from_str::<TransactionType>("\"custom:vote\"")?, custom);
+
+        assert!(serde_json::from_str::<TransactionType>("\"custom:\"").is_err());
+        assert!(serde_json::from_str::<TransactionType>("{\"Custom\":\"vote\"}").is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,115 @@
+use std::collections::HashSet;
+use std::sync::{RwLock, PoisonError};
+
+use super::*;
+
+#[derive(Default, Debug)]
+/// In-memory authorities list implementation.
+///
+/// This struct will keep authorities in the RAM
+/// and lose them once dropped.
+///
+/// Useful for tests and ephemeral nodes.
+pub struct MemoryAuthorities {
+    authorities: RwLock<HashSet<PublicKey>>
+}
+
+impl MemoryAuthorities {
+    #[inline]
+    /// Create empty authorities list.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl FromIterator<PublicKey> for MemoryAuthorities {
+    #[inline]
+    fn from_iter<T: IntoIterator<Item = PublicKey>>(iter: T) -> Self {
+        Self {
+            authorities: RwLock::new(HashSet::from_iter(iter))
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl AuthoritiesIndex for MemoryAuthorities {
+    type Error = std::convert::Infallible;
+
+    async fn get_authorities(&self) -> Result<HashSet<PublicKey>, Self::Error> {
+        let authorities = self.authorities.read()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        Ok(authorities.clone())
+    }
+
+    async fn insert_authority(&self, validator: PublicKey) -> Result<bool, Self::Error> {
+        let mut authorities = self.authorities.write()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        Ok(authorities.insert(validator))
+    }
+
+    async fn delete_authority(&self, validator: &PublicKey) -> Result<bool, Self::Error> {
+        let mut authorities = self.authorities.write()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        Ok(authorities.remove(validator))
+    }
+
+    async fn is_authority(&self, validator: &PublicKey) -> Result<bool, Self::Error> {
+        let authorities = self.authorities.read()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        Ok(authorities.contains(validator))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hyperborealib::exports::tokio;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn index() -> Result<(), std::convert::Infallible> {
+        use hyperborealib::crypto::asymmetric::SecretKey;
+
+        // Prepare authorities list
+        let authorities = [
+            SecretKey::random(),
+            SecretKey::random(),
+            SecretKey::random()
+        ];
+
+        // Run the tests
+        let index = MemoryAuthorities::new();
+
+        assert!(index.get_authorities().await?.is_empty());
+
+        assert!(!index.delete_authority(&authorities[0].public_key()).await?);
+        assert!(!index.is_authority(&authorities[0].public_key()).await?);
+
+        // Push 1 and 2
+        assert!(index.insert_authority(authorities[0].public_key()).await?);
+        assert!(index.insert_authority(authorities[1].public_key()).await?);
+        assert!(!index.insert_authority(authorities[1].public_key()).await?);
+
+        assert_eq!(index.get_authorities().await?, HashSet::from([
+            authorities[0].public_key(),
+            authorities[1].public_key()
+        ]));
+
+        assert!(index.is_authority(&authorities[0].public_key()).await?);
+        assert!(index.is_authority(&authorities[1].public_key()).await?);
+        assert!(!index.is_authority(&authorities[2].public_key()).await?);
+
+        // Delete 1 and 2
+        assert!(index.delete_authority(&authorities[0].public_key()).await?);
+        assert!(index.delete_authority(&authorities[1].public_key()).await?);
+        assert!(!index.delete_authority(&authorities[2].public_key()).await?);
+
+        assert!(index.get_authorities().await?.is_empty());
+
+        Ok(())
+    }
+}
@@ -3,8 +3,10 @@ use std::collections::HashSet;
 use hyperborealib::crypto::asymmetric::PublicKey;
 
 mod authorities_file;
+mod memory_authorities;
 
 pub use authorities_file::*;
+pub use memory_authorities::*;
 
 #[async_trait::async_trait]
 /// This trait implementation should hold information
@@ -7,7 +7,8 @@ use super::*;
 pub struct BasicBlockchain<A, B, C> {
     authorities_index: Arc<A>,
     blocks_index: Arc<B>,
-    transactions_index: Arc<C>
+    transactions_index: Arc<C>,
+    validation_context: ValidationContext
 }
 
 impl<A, B, C> BasicBlockchain<A, B, C> {
@@ -20,9 +21,62 @@ impl<A, B, C> BasicBlockchain<A, B, C> {
         Self {
             authorities_index,
             blocks_index,
-            transactions_index
+            transactions_index,
+            validation_context: ValidationContext::default()
         }
     }
+
+    #[inline]
+    /// Change context used to validate
+    /// blockchain's blocks and transactions.
+    pub fn with_validation_context(mut self, context: ValidationContext) -> Self {
+        self.validation_context = context;
+
+        self
+    }
+
+    #[inline]
+    /// Change identifier of the blockchain.
+    ///
+    /// Blocks and transactions made for
+    /// other blockchains will be rejected.
+    pub fn with_chain_id(mut self, chain_id: ChainId) -> Self {
+        self.validation_context.chain_id = Some(chain_id);
+
+        self
+    }
+}
+
+/// Blockchain stored entirely in the RAM.
+pub type MemoryBlockchain = BasicBlockchain<
+    MemoryAuthorities,
+    MemoryBlocksIndex,
+    MemoryTransactionsIndex<MemoryBlocksIndex>
+>;
+
+impl MemoryBlockchain {
+    /// Create new empty in-memory blockchain
+    /// with given authorities.
+    ///
+    /// ```
+    /// use hyperborealib::prelude::*;
+    /// use hyperchain::prelude::*;
+    ///
+    /// let authority = SecretKey::random();
+    ///
+    /// let blockchain = MemoryBlockchain::in_memory([
+    ///     authority.public_key()
+    /// ]);
+    /// ```
+    pub fn in_memory(authorities: impl IntoIterator<Item = PublicKey>) -> Self {
+        let blocks_index = Arc::new(MemoryBlocksIndex::new());
+
+        Self::new(
+            Arc::new(MemoryAuthorities::from_iter(authorities)),
+            blocks_index.clone(),
+            Arc::new(MemoryTransactionsIndex::new(blocks_index))
+        )
+    }
 }
 
 impl<A, B, C> Blockchain for BasicBlockchain<A, B, C>
@@ -64,4 +118,531 @@ where
     fn transactions_index_ref(&self) ->  &Self::TransactionsIndex {
         &self.transactions_index
     }
+
+    #[inline]
+    fn validation_context(&self) -> ValidationContext {
+        self.validation_context.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hyperborealib::exports::tokio;
+    use hyperborealib::crypto::asymmetric::SecretKey;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn validate() -> Result<(), BlockchainValidationError<std::convert::Infallible, std::convert::Infallible, std::convert::Infallible>> {
+        let authority = SecretKey::random();
+
+        let blockchain = MemoryBlockchain::in_memory([
+            authority.public_key()
+        ]);
+
+        let block_a = BlockBuilder::build_root(&authority);
+        let block_b = BlockBuilder::chained(&block_a).sign(&authority);
+        let block_c = BlockBuilder::chained(&block_b).sign(&SecretKey::random());
+
+        assert_eq!(blockchain.validate().await?, BlockchainValidationResult::Valid);
+
+        blockchain.blocks_index_ref().insert_block(block_a).await.unwrap();
+        blockchain.blocks_index_ref().insert_block(block_b).await.unwrap();
+
+        assert_eq!(blockchain.validate().await?, BlockchainValidationResult::Valid);
+
+        blockchain.blocks_index_ref().insert_block(block_c.clone()).await.unwrap();
+
+        assert_eq!(blockchain.validate().await?, BlockchainValidationResult::InvalidValidator {
+            block_number: 2,
+            validator: block_c.validator().clone()
+        });
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn validate_genesis_chain_id() -> Result<(), BlockchainValidationError<std::convert::Infallible, std::convert::Infallible, std::convert::Infallible>> {
+        let authority = SecretKey::random();
+
+        let block_a = BlockBuilder::new()
+            .add_transaction(TransactionBuilder::new()
+                .with_body(TransactionBody::Raw(b"Hello, World!".to_vec()))
+                .sign(&authority)
+                .unwrap())
+            .sign(&authority);
+
+        let chain_id = ChainId::from_genesis(&block_a);
+
+        let blockchain = MemoryBlockchain::in_memory([
+            authority.public_key()
+        ]).with_chain_id(chain_id);
+
+        let block_b = BlockBuilder::chained(&block_a)
+            .with_chain_id(chain_id)
+            .add_transaction(TransactionBuilder::new()
+                .with_chain_id(chain_id)
+                .with_body(TransactionBody::Raw(b"Hello, Genesis!".to_vec()))
+                .sign(&authority)
+                .unwrap())
+            .sign(&authority);
+
+        blockchain.blocks_index_ref().insert_block(block_a).await.unwrap();
+        blockchain.blocks_index_ref().insert_block(block_b.clone()).await.unwrap();
+
+        assert_eq!(blockchain.validate().await?, BlockchainValidationResult::Valid);
+
+        // Other blocks must be bound to the chain
+        let block_c = BlockBuilder::chained(&block_b).sign(&authority);
+
+        blockchain.blocks_index_ref().insert_block(block_c).await.unwrap();
+
+        assert!(matches!(blockchain.validate().await?, BlockchainValidationResult::InvalidSign {
+            block_number: 2,
+            reason: BlockValidationResult::InvalidChainId { .. },
+            ..
+        }));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn validate_duplicates() -> Result<(), BlockchainValidationError<std::convert::Infallible, std::convert::Infallible, std::convert::Infallible>> {
+        let authority = SecretKey::random();
+
+        let blockchain = MemoryBlockchain::in_memory([
+            authority.public_key()
+        ]);
+
+        let transaction = TransactionBuilder::new()
+            .with_body(TransactionBody::Raw(b"Hello, World!".to_vec()))
+            .sign(&authority)
+            .unwrap();
+
+        let block_a = BlockBuilder::build_root(&authority);
+
+        let block_b = BlockBuilder::chained(&block_a)
+            .add_transaction(transaction.clone())
+            .sign(&authority);
+
+        let block_c = BlockBuilder::chained(&block_b)
+            .add_transaction(transaction.clone())
+            .sign(&authority);
+
+        for block in [block_a, block_b, block_c] {
+            blockchain.blocks_index_ref().insert_block(block).await.unwrap();
+        }
+
+        let duplicate = BlockchainValidationResult::DuplicateTransaction {
+            transaction: transaction.get_hash(),
+            first_block: 1,
+            duplicate_block: 2
+        };
+
+        assert_eq!(blockchain.validate().await?, duplicate);
+
+        // Earlier blocks are searched in the transactions index
+        assert_eq!(blockchain.validate_since(2).await?, duplicate);
+        assert_eq!(blockchain.validate_parallel(2, ParallelValidationOptions::default(), |_| ()).await?, duplicate);
+
+        assert!(blockchain.validate_since_report(2).await?.findings().iter()
+            .any(|finding| matches!(&finding.issue, ValidationIssue::Blockchain(issue) if issue == &duplicate)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn validate_nonces() -> Result<(), BlockchainValidationError<std::convert::Infallible, std::convert::Infallible, std::convert::Infallible>> {
+        let authority = SecretKey::random();
+
+        let blockchain = MemoryBlockchain::in_memory([
+            authority.public_key()
+        ]).with_validation_context(ValidationContext::default().with_required_nonces(true));
+
+        let transaction = |nonce: u64| {
+            TransactionBuilder::new()
+                .with_nonce(nonce)
+                .with_body(TransactionBody::Raw(nonce.to_be_bytes().to_vec()))
+                .sign(&authority)
+                .unwrap()
+        };
+
+        let block_a = BlockBuilder::build_root(&authority);
+
+        let block_b = BlockBuilder::chained(&block_a)
+            .add_transaction(transaction(0))
+            .add_transaction(transaction(1))
+            .sign(&authority);
+
+        let block_c = BlockBuilder::chained(&block_b)
+            .add_transaction(transaction(3))
+            .sign(&authority);
+
+        for block in [block_a, block_b, block_c.clone()] {
+            blockchain.blocks_index_ref().insert_block(block).await.unwrap();
+        }
+
+        let invalid_nonce = BlockchainValidationResult::InvalidNonce {
+            block_number: 2,
+            transaction: block_c.transactions()[0].get_hash(),
+            expected: 2,
+            got: 3
+        };
+
+        assert_eq!(blockchain.validate().await?, invalid_nonce);
+
+        // Earlier nonces are loaded from the transactions index
+        assert_eq!(blockchain.validate_since(2).await?, invalid_nonce);
+
+        // First nonce of the author must be zero
+        let blockchain = MemoryBlockchain::in_memory([
+            authority.public_key()
+        ]).with_validation_context(ValidationContext::default().with_required_nonces(true));
+
+        let block_a = BlockBuilder::build_root(&authority);
+
+        let block_b = BlockBuilder::chained(&block_a)
+            .add_transaction(transaction(1))
+            .sign(&authority);
+
+        for block in [block_a, block_b.clone()] {
+            blockchain.blocks_index_ref().insert_block(block).await.unwrap();
+        }
+
+        assert_eq!(blockchain.validate().await?, BlockchainValidationResult::InvalidNonce {
+            block_number: 1,
+            transaction: block_b.transactions()[0].get_hash(),
+            expected: 0,
+            got: 1
+        });
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn validate_references() -> Result<(), BlockchainValidationError<std::convert::Infallible, std::convert::Infallible, std::convert::Infallible>> {
+        let authority = SecretKey::random();
+
+        let blockchain = MemoryBlockchain::in_memory([
+            authority.public_key()
+        ]).with_validation_context(ValidationContext::default().with_required_references(true));
+
+        let post = TransactionBuilder::new()
+            .with_body(TransactionBody::Raw(b"Hello, World!".to_vec()))
+            .sign(&authority)
+            .unwrap();
+
+        let reply = TransactionBuilder::new()
+            .with_reference(post.get_hash())
+            .with_body(TransactionBody::Raw(b"Hello, Post!".to_vec()))
+            .sign(&authority)
+            .unwrap();
+
+        let orphan = TransactionBuilder::new()
+            .with_reference(Hash::hash_slice(b"Unknown"))
+            .with_body(TransactionBody::Raw(b"Hello, Unknown!".to_vec()))
+            .sign(&authority)
+            .unwrap();
+
+        let block_a = BlockBuilder::build_root(&authority);
+
+        let block_b = BlockBuilder::chained(&block_a)
+            .add_transaction(post)
+            .add_transaction(reply)
+            .sign(&authority);
+
+        blockchain.blocks_index_ref().insert_block(block_a).await.unwrap();
+        blockchain.blocks_index_ref().insert_block(block_b.clone()).await.unwrap();
+
+        assert_eq!(blockchain.validate().await?, BlockchainValidationResult::Valid);
+
+        let block_c = BlockBuilder::chained(&block_b)
+            .add_transaction(orphan.clone())
+            .sign(&authority);
+
+        blockchain.blocks_index_ref().insert_block(block_c).await.unwrap();
+
+        let unknown_reference = BlockchainValidationResult::UnknownReference {
+            block_number: 2,
+            transaction: orphan.get_hash(),
+            reference: Hash::hash_slice(b"Unknown")
+        };
+
+        assert_eq!(blockchain.validate().await?, unknown_reference);
+
+        // Earlier transactions are loaded from the transactions index
+        assert_eq!(blockchain.validate_since(2).await?, unknown_reference);
+        assert_eq!(blockchain.validate_parallel(2, ParallelValidationOptions::default(), |_| ()).await?, unknown_reference);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn validate_incremental_references() -> Result<(), IncrementalValidationError<std::convert::Infallible, std::convert::Infallible, std::convert::Infallible, std::convert::Infallible>> {
+        let authority = SecretKey::random();
+
+        let blockchain = MemoryBlockchain::in_memory([
+            authority.public_key()
+        ]).with_validation_context(ValidationContext::default().with_required_references(true));
+
+        let checkpoints = MemoryValidationCheckpoint::new();
+
+        let post = TransactionBuilder::new()
+            .with_body(TransactionBody::Raw(b"Hello, World!".to_vec()))
+            .sign(&authority)
+            .unwrap();
+
+        let reply = TransactionBuilder::new()
+            .with_reference(post.get_hash())
+            .with_body(TransactionBody::Raw(b"Hello, Post!".to_vec()))
+            .sign(&authority)
+            .unwrap();
+
+        let orphan = TransactionBuilder::new()
+            .with_reference(Hash::hash_slice(b"Unknown"))
+            .with_body(TransactionBody::Raw(b"Hello, Unknown!".to_vec()))
+            .sign(&authority)
+            .unwrap();
+
+        let block_a = BlockBuilder::build_root(&authority);
+
+        let block_b = BlockBuilder::chained(&block_a)
+            .add_transaction(post)
+            .sign(&authority);
+
+        // Push A and B
+        blockchain.blocks_index_ref().insert_block(block_a).await.unwrap();
+        blockchain.blocks_index_ref().insert_block(block_b.clone()).await.unwrap();
+
+        assert_eq!(blockchain.validate_incremental(&checkpoints).await?, BlockchainValidationResult::Valid);
+        assert_eq!(checkpoints.get_checkpoint().await.unwrap(), Some(ValidationCheckpoint::from(&block_b)));
+
+        // Push C replying to the checkpointed B
+        let block_c = BlockBuilder::chained(&block_b)
+            .add_transaction(reply)
+            .sign(&authority);
+
+        blockchain.blocks_index_ref().insert_block(block_c.clone()).await.unwrap();
+
+        assert_eq!(blockchain.validate_incremental(&checkpoints).await?, BlockchainValidationResult::Valid);
+        assert_eq!(checkpoints.get_checkpoint().await.unwrap(), Some(ValidationCheckpoint::from(&block_c)));
+
+        // Push D referencing unknown transaction
+        let block_d = BlockBuilder::chained(&block_c)
+            .add_transaction(orphan.clone())
+            .sign(&authority);
+
+        blockchain.blocks_index_ref().insert_block(block_d).await.unwrap();
+
+        assert_eq!(blockchain.validate_incremental(&checkpoints).await?, BlockchainValidationResult::UnknownReference {
+            block_number: 3,
+            transaction: orphan.get_hash(),
+            reference: Hash::hash_slice(b"Unknown")
+        });
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn validate_delegation() -> Result<(), BlockchainValidationError<std::convert::Infallible, std::convert::Infallible, std::convert::Infallible>> {
+        let authority = SecretKey::random();
+        let session = SecretKey::random();
+
+        let blockchain = MemoryBlockchain::in_memory([
+            authority.public_key()
+        ]);
+
+        let certificate = DelegationBuilder::new(session.public_key())
+            .with_height_bounds(1, 1)
+            .sign(&authority);
+
+        let block_a = BlockBuilder::build_root(&authority);
+
+        let block_b = BlockBuilder::chained(&block_a)
+            .with_delegation(certificate.clone())
+            .sign(&session);
+
+        blockchain.blocks_index_ref().insert_block(block_a).await.unwrap();
+        blockchain.blocks_index_ref().insert_block(block_b.clone()).await.unwrap();
+
+        assert_eq!(blockchain.validate().await?, BlockchainValidationResult::Valid);
+
+        // Certificate doesn't cover the third block
+        let block_c = BlockBuilder::chained(&block_b)
+            .with_delegation(certificate)
+            .sign(&session);
+
+        blockchain.blocks_index_ref().insert_block(block_c).await.unwrap();
+
+        assert!(matches!(blockchain.validate().await?, BlockchainValidationResult::InvalidSign {
+            block_number: 2,
+            reason: BlockValidationResult::InvalidDelegation {
+                issue: DelegationIssue::OutOfHeightBounds,
+                ..
+            },
+            ..
+        }));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn validate_incremental() -> Result<(), IncrementalValidationError<std::convert::Infallible, std::convert::Infallible, std::convert::Infallible, std::convert::Infallible>> {
+        let authority = SecretKey::random();
+
+        let blockchain = MemoryBlockchain::in_memory([
+            authority.public_key()
+        ]);
+
+        let checkpoints = MemoryValidationCheckpoint::new();
+
+        let block_a = BlockBuilder::build_root(&authority);
+        let block_b = BlockBuilder::chained(&block_a).sign(&authority);
+        let block_c = BlockBuilder::chained(&block_b).sign(&authority);
+
+        assert_eq!(blockchain.validate_incremental(&checkpoints).await?, BlockchainValidationResult::Valid);
+        assert!(checkpoints.get_checkpoint().await.unwrap().is_none());
+
+        // Push A and B
+        blockchain.blocks_index_ref().insert_block(block_a.clone()).await.unwrap();
+        blockchain.blocks_index_ref().insert_block(block_b.clone()).await.unwrap();
+
+        assert_eq!(blockchain.validate_incremental(&checkpoints).await?, BlockchainValidationResult::Valid);
+        assert_eq!(checkpoints.get_checkpoint().await.unwrap(), Some(ValidationCheckpoint::from(&block_b)));
+
+        // Push C
+        blockchain.blocks_index_ref().insert_block(block_c.clone()).await.unwrap();
+
+        assert_eq!(blockchain.validate_incremental(&checkpoints).await?, BlockchainValidationResult::Valid);
+        assert_eq!(checkpoints.get_checkpoint().await.unwrap(), Some(ValidationCheckpoint::from(&block_c)));
+
+        // Replace C
+        let block_c_alt = BlockBuilder::chained(&block_b).sign(&authority);
+
+        blockchain.blocks_index_ref().truncate_after(1).await.unwrap();
+        blockchain.blocks_index_ref().insert_block(block_c_alt.clone()).await.unwrap();
+
+        assert_eq!(blockchain.validate_incremental(&checkpoints).await?, BlockchainValidationResult::CheckpointMismatch {
+            block_number: 2,
+            expected_hash: block_c.get_hash(),
+            got_hash: Some(block_c_alt.get_hash())
+        });
+
+        assert!(checkpoints.get_checkpoint().await.unwrap().is_none());
+
+        assert_eq!(blockchain.validate_incremental(&checkpoints).await?, BlockchainValidationResult::Valid);
+        assert_eq!(checkpoints.get_checkpoint().await.unwrap(), Some(ValidationCheckpoint::from(&block_c_alt)));
+
+        // Replace B, keeping the checkpointed C
+        let block_b_alt = BlockBuilder::chained(&block_a).sign(&authority);
+
+        blockchain.blocks_index_ref().truncate_after(0).await.unwrap();
+        blockchain.blocks_index_ref().insert_block(block_b_alt.clone()).await.unwrap();
+        blockchain.blocks_index_ref().insert_block(block_c_alt.clone()).await.unwrap();
+
+        // Only the checkpointed block is verified
+        assert_eq!(blockchain.validate_incremental(&checkpoints).await?, BlockchainValidationResult::Valid);
+
+        assert!(matches!(blockchain.validate().await?, BlockchainValidationResult::InvalidPreviosBlockReference {
+            block_number: 2,
+            ..
+        }));
+
+        // Modify the checkpointed block
+        let mut block_c_modified = block_c_alt.clone();
+
+        block_c_modified.created_at += 1;
+
+        blockchain.blocks_index_ref().truncate_after(1).await.unwrap();
+        blockchain.blocks_index_ref().insert_block(block_c_modified).await.unwrap();
+
+        assert_eq!(blockchain.validate_incremental(&checkpoints).await?, BlockchainValidationResult::CheckpointMismatch {
+            block_number: 2,
+            expected_hash: block_c_alt.get_hash(),
+            got_hash: Some(block_c_alt.get_hash())
+        });
+
+        assert!(checkpoints.get_checkpoint().await.unwrap().is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn validate_parallel() -> Result<(), BlockchainValidationError<std::convert::Infallible, std::convert::Infallible, std::convert::Infallible>> {
+        let authority = SecretKey::random();
+
+        let blockchain = MemoryBlockchain::in_memory([
+            authority.public_key()
+        ]);
+
+        let options = ParallelValidationOptions {
+            workers: 2,
+            batch_size: 3
+        };
+
+        // Push 10 blocks
+        let mut block = BlockBuilder::build_root(&authority);
+
+        for _ in 0..10 {
+            blockchain.blocks_index_ref().insert_block(block.clone()).await.unwrap();
+
+            block = BlockBuilder::chained(&block).sign(&authority);
+        }
+
+        let mut progress = Vec::new();
+
+        let result = blockchain.validate_parallel(0, options, |status| progress.push(status)).await?;
+
+        assert_eq!(result, BlockchainValidationResult::Valid);
+
+        assert_eq!(progress.len(), 4);
+        assert_eq!(progress.last().map(|status| (status.block_number, status.validated_blocks)), Some((9, 10)));
+
+        // Push block signed by a stranger
+        let stranger_block = BlockBuilder::chained(&blockchain.blocks_index_ref().get_tail_block().await.unwrap().unwrap())
+            .sign(&SecretKey::random());
+
+        blockchain.blocks_index_ref().insert_block(stranger_block.clone()).await.unwrap();
+
+        let result = blockchain.validate_parallel(0, options, |_| ()).await?;
+
+        assert_eq!(result, BlockchainValidationResult::InvalidValidator {
+            block_number: 10,
+            validator: stranger_block.validator().clone()
+        });
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn validate_since_report() -> Result<(), BlockchainValidationError<std::convert::Infallible, std::convert::Infallible, std::convert::Infallible>> {
+        let authority = SecretKey::random();
+        let stranger = SecretKey::random();
+
+        let blockchain = MemoryBlockchain::in_memory([
+            authority.public_key()
+        ]);
+
+        let block_a = BlockBuilder::build_root(&authority);
+        let block_b = BlockBuilder::chained(&block_a).sign(&stranger);
+        let block_c = BlockBuilder::chained(&block_b).sign(&stranger);
+
+        blockchain.blocks_index_ref().insert_block(block_a).await.unwrap();
+        blockchain.blocks_index_ref().insert_block(block_b.clone()).await.unwrap();
+        blockchain.blocks_index_ref().insert_block(block_c.clone()).await.unwrap();
+
+        let report = blockchain.validate_since_report(0).await?;
+
+        assert!(!report.is_valid());
+
+        assert_eq!(report.validated_blocks(), 3);
+
+        let invalid_blocks = report.with_severity(ValidationSeverity::Error)
+            .map(|finding| finding.block_number)
+            .collect::<Vec<_>>();
+
+        assert_eq!(invalid_blocks, vec![Some(1), Some(2)]);
+
+        Ok(())
+    }
 }
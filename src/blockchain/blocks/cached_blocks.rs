@@ -0,0 +1,428 @@
+use std::collections::{HashMap, BTreeMap};
+use std::sync::{Mutex, PoisonError};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use super::*;
+
+#[derive(Debug)]
+struct CachedBlocksState {
+    /// Cached blocks with their last use tick.
+    blocks: HashMap<u64, (Block, u64)>,
+
+    /// Cached blocks numbers sorted by their last use tick.
+    usage: BTreeMap<u64, u64>,
+
+    /// Counter of blocks uses.
+    tick: u64,
+
+    /// Maximal amount of cached blocks.
+    capacity: usize,
+
+    /// Cached head block, `None` if not cached.
+    head_block: Option<Option<Block>>,
+
+    /// Cached tail block, `None` if not cached.
+    tail_block: Option<Option<Block>>,
+
+    /// Counter of the wrapped index changes.
+    ///
+    /// Values read from the wrapped index are cached
+    /// only if it wasn't changed during the read.
+    generation: u64
+}
+
+impl CachedBlocksState {
+    fn new(capacity: usize) -> Self {
+        Self {
+            blocks: HashMap::with_capacity(capacity),
+            usage: BTreeMap::new(),
+            tick: 0,
+            capacity,
+            head_block: None,
+            tail_block: None,
+            generation: 0
+        }
+    }
+
+    /// Get cached block and mark it as recently used.
+    fn get(&mut self, number: u64) -> Option<Block> {
+        self.tick += 1;
+
+        let (block, tick) = self.blocks.get_mut(&number)?;
+
+        self.usage.remove(&*tick);
+        self.usage.insert(self.tick, number);
+
+        *tick = self.tick;
+
+        Some(block.clone())
+    }
+
+    /// Cache block, removing the least recently used one
+    /// if the cache is full.
+    fn put(&mut self, block: Block) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        self.tick += 1;
+
+        if let Some((_, tick)) = self.blocks.remove(&block.number()) {
+            self.usage.remove(&tick);
+        }
+
+        while self.blocks.len() >= self.capacity {
+            let Some((_, number)) = self.usage.pop_first() else {
+                break;
+            };
+
+            self.blocks.remove(&number);
+        }
+
+        self.usage.insert(self.tick, block.number());
+        self.blocks.insert(block.number(), (block, self.tick));
+    }
+
+    /// Mark the wrapped index as changed
+    /// and drop its cached head and tail blocks.
+    fn invalidate(&mut self) {
+        self.generation += 1;
+
+        self.head_block = None;
+        self.tail_block = None;
+    }
+
+    /// Remove all the cached blocks with numbers
+    /// higher than the given one.
+    fn remove_after(&mut self, number: u64) {
+        let usage = &mut self.usage;
+
+        self.blocks.retain(|block_number, (_, tick)| {
+            if *block_number > number {
+                usage.remove(&*tick);
+
+                return false;
+            }
+
+            true
+        });
+    }
+}
+
+/// Caching blocks index wrapper.
+///
+/// This struct will keep recently used blocks
+/// of the wrapped blocks index in the RAM
+/// together with its head and tail blocks.
+///
+/// Useful for blocks indexes with slow reads
+/// like `ChunkedBlocksIndex`.
+pub struct CachedBlocksIndex<T> {
+    index: T,
+    state: Mutex<CachedBlocksState>,
+    hits: AtomicU64,
+    misses: AtomicU64
+}
+
+impl<T: BlocksIndex> CachedBlocksIndex<T> {
+    #[inline]
+    /// Wrap given blocks index.
+    ///
+    /// Capacity specifies maximal amount
+    /// of blocks to keep in the cache.
+    pub fn new(index: T, capacity: usize) -> Self {
+        Self {
+            index,
+            state: Mutex::new(CachedBlocksState::new(capacity)),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0)
+        }
+    }
+
+    #[inline]
+    /// Get reference to the wrapped blocks index.
+    pub fn inner(&self) -> &T {
+        &self.index
+    }
+
+    #[inline]
+    /// Amount of reads served from the cache.
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    #[inline]
+    /// Amount of reads passed to the wrapped index.
+    pub fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    /// Remove all the cached values.
+    pub fn clear(&self) {
+        let mut state = self.lock();
+
+        let capacity = state.capacity;
+        let generation = state.generation;
+
+        *state = CachedBlocksState::new(capacity);
+
+        state.generation = generation + 1;
+    }
+
+    #[inline]
+    fn lock(&self) -> std::sync::MutexGuard<'_, CachedBlocksState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Cache block read from the wrapped index
+    /// if it wasn't changed since the given generation.
+    fn cache_block(&self, block: &Block, generation: u64) {
+        let mut state = self.lock();
+
+        if state.generation == generation {
+            state.put(block.clone());
+        }
+    }
+
+    #[inline]
+    fn hit(&self) {
+        self.hits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[inline]
+    fn miss(&self) {
+        self.misses.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[async_trait::async_trait]
+impl<T> BlocksIndex for CachedBlocksIndex<T>
+where T: BlocksIndex + Send + Sync
+{
+    type Error = T::Error;
+
+    async fn get_block(&self, number: u64) -> Result<Option<Block>, Self::Error> {
+        let (cached_block, generation) = {
+            let mut state = self.lock();
+
+            (state.get(number), state.generation)
+        };
+
+        if let Some(block) = cached_block {
+            self.hit();
+
+            return Ok(Some(block));
+        }
+
+        self.miss();
+
+        let block = self.index.get_block(number).await?;
+
+        if let Some(block) = &block {
+            self.cache_block(block, generation);
+        }
+
+        Ok(block)
+    }
+
+    async fn insert_block(&self, block: Block) -> Result<bool, Self::Error> {
+        let result = self.index.insert_block(block).await?;
+
+        // Head and tail blocks could be changed
+        // by the new block.
+        if result {
+            self.lock().invalidate();
+        }
+
+        Ok(result)
+    }
+
+    async fn truncate_after(&self, number: u64) -> Result<Vec<Block>, Self::Error> {
+        let removed_blocks = self.index.truncate_after(number).await?;
+
+        let mut state = self.lock();
+
+        state.remove_after(number);
+        state.invalidate();
+
+        Ok(removed_blocks)
+    }
+
+    async fn get_next_block(&self, block: &Block) -> Result<Option<Block>, Self::Error> {
+        let (cached_block, generation) = {
+            let mut state = self.lock();
+
+            (state.get(block.number() + 1), state.generation)
+        };
+
+        // Cached block could belong to another fork
+        // so it's used only if it follows the given one.
+        let cached_block = cached_block
+            .filter(|next_block| next_block.previous_block() == Some(block.get_hash()));
+
+        if let Some(block) = cached_block {
+            self.hit();
+
+            return Ok(Some(block));
+        }
+
+        self.miss();
+
+        let next_block = self.index.get_next_block(block).await?;
+
+        if let Some(next_block) = &next_block {
+            self.cache_block(next_block, generation);
+        }
+
+        Ok(next_block)
+    }
+
+    async fn get_head_block(&self) -> Result<Option<Block>, Self::Error> {
+        let (cached_block, generation) = {
+            let state = self.lock();
+
+            (state.head_block.clone(), state.generation)
+        };
+
+        if let Some(block) = cached_block {
+            self.hit();
+
+            return Ok(block);
+        }
+
+        self.miss();
+
+        let block = self.index.get_head_block().await?;
+
+        let mut state = self.lock();
+
+        if state.generation == generation {
+            state.head_block = Some(block.clone());
+        }
+
+        Ok(block)
+    }
+
+    async fn get_tail_block(&self) -> Result<Option<Block>, Self::Error> {
+        let (cached_block, generation) = {
+            let state = self.lock();
+
+            (state.tail_block.clone(), state.generation)
+        };
+
+        if let Some(block) = cached_block {
+            self.hit();
+
+            return Ok(block);
+        }
+
+        self.miss();
+
+        let block = self.index.get_tail_block().await?;
+
+        let mut state = self.lock();
+
+        if state.generation == generation {
+            state.tail_block = Some(block.clone());
+        }
+
+        Ok(block)
+    }
+
+    async fn is_empty(&self) -> Result<bool, Self::Error> {
+        let cached_block = self.lock().head_block.clone();
+
+        if let Some(block) = cached_block {
+            self.hit();
+
+            return Ok(block.is_none());
+        }
+
+        self.miss();
+
+        self.index.is_empty().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hyperborealib::exports::tokio;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn index() -> Result<(), std::convert::Infallible> {
+        use hyperborealib::crypto::asymmetric::SecretKey;
+
+        use crate::block::prelude::*;
+
+        // Prepare blocks
+        let validator = SecretKey::random();
+
+        let block_a = BlockBuilder::build_root(&validator);
+        let block_b = BlockBuilder::chained(&block_a).sign(&validator);
+        let block_c = BlockBuilder::chained(&block_b).sign(&validator);
+
+        // Run the tests
+        let index = CachedBlocksIndex::new(MemoryBlocksIndex::new(), 2);
+
+        assert!(index.get_head_block().await?.is_none());
+        assert!(index.get_head_block().await?.is_none());
+
+        assert_eq!((index.hits(), index.misses()), (1, 1));
+
+        // Push A and B
+        assert!(index.insert_block(block_a.clone()).await?);
+        assert!(index.insert_block(block_b.clone()).await?);
+
+        // Head block cache should be invalidated
+        assert_eq!(index.get_head_block().await?, Some(block_a.clone()));
+        assert_eq!(index.get_tail_block().await?, Some(block_b.clone()));
+        assert_eq!(index.get_tail_block().await?, Some(block_b.clone()));
+
+        assert_eq!((index.hits(), index.misses()), (2, 3));
+
+        // Cache A and B
+        assert_eq!(index.get_block(0).await?, Some(block_a.clone()));
+        assert_eq!(index.get_next_block(&block_a).await?, Some(block_b.clone()));
+        assert_eq!(index.get_block(0).await?, Some(block_a.clone()));
+        assert_eq!(index.get_block(1).await?, Some(block_b.clone()));
+
+        assert_eq!((index.hits(), index.misses()), (4, 5));
+
+        // Cached B doesn't follow A from another fork
+        let block_a_fork = BlockBuilder::build_root(&validator);
+
+        assert_eq!(index.get_next_block(&block_a_fork).await?, Some(block_b.clone()));
+        assert_eq!((index.hits(), index.misses()), (4, 6));
+
+        // Push C, removing A from the cache
+        assert!(index.insert_block(block_c.clone()).await?);
+
+        assert_eq!(index.get_block(2).await?, Some(block_c.clone()));
+        assert_eq!(index.get_block(1).await?, Some(block_b.clone()));
+        assert_eq!(index.get_block(0).await?, Some(block_a.clone()));
+
+        assert_eq!((index.hits(), index.misses()), (5, 8));
+
+        assert_eq!(index.get_tail_block().await?, Some(block_c.clone()));
+
+        // Truncate C
+        assert_eq!(index.truncate_after(1).await?, vec![block_c]);
+
+        assert!(index.get_block(2).await?.is_none());
+        assert_eq!(index.get_tail_block().await?, Some(block_b.clone()));
+
+        // Blocks read before truncation are not cached
+        let generation = index.lock().generation;
+
+        assert!(index.insert_block(block_c.clone()).await?);
+        assert_eq!(index.truncate_after(1).await?, vec![block_c.clone()]);
+
+        index.cache_block(&block_c, generation);
+
+        assert!(index.get_block(2).await?.is_none());
+
+        Ok(())
+    }
+}
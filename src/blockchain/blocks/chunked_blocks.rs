@@ -57,6 +57,13 @@ impl ChunkedBlocksIndex {
             tail_block: AtomicU64::new(0)
         })
     }
+
+    /// Parse chunk number from its file name.
+    fn parse_chunk_number(name: &str) -> Option<u64> {
+        name.strip_prefix("chunk-")
+            .and_then(|tail| tail.strip_suffix(".json"))
+            .and_then(|number| number.parse::<u64>().ok())
+    }
 }
 
 #[async_trait::async_trait]
@@ -113,6 +120,69 @@ impl BlocksIndex for ChunkedBlocksIndex {
         Ok(true)
     }
 
+    async fn truncate_after(&self, number: u64) -> Result<Vec<Block>, Self::Error> {
+        let mut chunks = tokio::fs::read_dir(&self.folder).await?;
+        let mut removed_blocks = Vec::new();
+
+        // Number of the chunk which could store the given block.
+        let first_chunk = number / self.chunk_size;
+
+        while let Some(entry) = chunks.next_entry().await? {
+            let name = entry.file_name()
+                .to_string_lossy()
+                .to_string();
+
+            // Skip chunks which can't contain newer blocks.
+            let Some(chunk_number) = Self::parse_chunk_number(&name) else {
+                continue;
+            };
+
+            if chunk_number < first_chunk {
+                continue;
+            }
+
+            let chunk_path = entry.path();
+
+            let chunk = tokio::fs::read(&chunk_path).await?;
+            let chunk = serde_json::from_slice::<HashSet<Json>>(&chunk)?;
+
+            let mut kept_blocks = HashSet::with_capacity(chunk.len());
+            let mut chunk_updated = false;
+
+            // Split chunk's blocks into kept and removed ones.
+            for block_json in chunk {
+                match Block::from_json(&block_json) {
+                    Ok(block) if block.number() > number => {
+                        removed_blocks.push(block);
+
+                        chunk_updated = true;
+                    }
+
+                    _ => {
+                        kept_blocks.insert(block_json);
+                    }
+                }
+            }
+
+            // Remove the chunk file if it doesn't have any blocks anymore.
+            if kept_blocks.is_empty() {
+                tokio::fs::remove_file(&chunk_path).await?;
+            }
+
+            // Otherwise update it if some blocks were removed.
+            else if chunk_updated {
+                tokio::fs::write(&chunk_path, serde_json::to_string_pretty(&kept_blocks)?).await?;
+            }
+        }
+
+        // Reset cached tail block number if it was removed.
+        self.tail_block.fetch_min(number, Ordering::Release);
+
+        removed_blocks.sort_by_key(|block| block.number());
+
+        Ok(removed_blocks)
+    }
+
     async fn get_head_block(&self) -> Result<Option<Block>, Self::Error> {
         let mut chunks = tokio::fs::read_dir(&self.folder).await?;
         let mut head_chunk = None;
@@ -123,16 +193,12 @@ impl BlocksIndex for ChunkedBlocksIndex {
                 .to_string_lossy()
                 .to_string();
 
-            if let Some(tail) = name.strip_prefix("chunk-") {
-                if let Some(number) = tail.strip_suffix(".json") {
-                    if let Ok(number) = number.parse::<u64>() {
-                        head_chunk = match head_chunk {
-                            Some(head_chunk) if number < head_chunk => Some(number),
-                            None => Some(number),
-                            _ => head_chunk
-                        };
-                    }
-                }
+            if let Some(number) = Self::parse_chunk_number(&name) {
+                head_chunk = match head_chunk {
+                    Some(head_chunk) if number < head_chunk => Some(number),
+                    None => Some(number),
+                    _ => head_chunk
+                };
             }
         }
 
@@ -331,4 +397,57 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn truncate() -> Result<(), ChunkedBlocksIndexError> {
+        use hyperborealib::crypto::asymmetric::SecretKey;
+
+        use crate::block::prelude::*;
+
+        let path = crate::utils::test_dir("chunked-blocks-truncate-test")?;
+
+        // Prepare blocks
+        let validator = SecretKey::random();
+
+        let block_a = BlockBuilder::build_root(&validator);
+        let block_b = BlockBuilder::chained(&block_a).sign(&validator);
+        let block_c = BlockBuilder::chained(&block_b).sign(&validator);
+        let block_d = BlockBuilder::chained(&block_c).sign(&validator);
+
+        // Run the tests
+        let index = ChunkedBlocksIndex::open(&path, 2).await?;
+
+        assert!(index.truncate_after(0).await?.is_empty());
+
+        for block in [&block_a, &block_b, &block_c, &block_d] {
+            assert!(index.insert_block(block.clone()).await?);
+        }
+
+        assert_eq!(index.get_tail_block().await?, Some(block_d.clone()));
+
+        // Truncate C and D
+        assert_eq!(index.truncate_after(1).await?, vec![block_c.clone(), block_d.clone()]);
+
+        assert_eq!(index.get_block(1).await?, Some(block_b.clone()));
+        assert!(index.get_block(2).await?.is_none());
+        assert!(index.get_block(3).await?.is_none());
+
+        assert_eq!(index.get_head_block().await?, Some(block_a.clone()));
+        assert_eq!(index.get_tail_block().await?, Some(block_b.clone()));
+
+        // Truncate B
+        assert_eq!(index.truncate_after(0).await?, vec![block_b.clone()]);
+
+        assert_eq!(index.get_tail_block().await?, Some(block_a.clone()));
+
+        // Push B and C back
+        assert!(index.insert_block(block_b.clone()).await?);
+        assert!(index.insert_block(block_c.clone()).await?);
+
+        assert_eq!(index.get_tail_block().await?, Some(block_c.clone()));
+
+        tokio::fs::remove_dir_all(&path).await?;
+
+        Ok(())
+    }
 }
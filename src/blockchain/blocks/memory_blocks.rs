@@ -0,0 +1,163 @@
+use std::collections::BTreeMap;
+use std::sync::{RwLock, PoisonError};
+
+use super::*;
+
+#[derive(Default, Debug)]
+/// In-memory blocks index implementation.
+///
+/// This struct will keep blocks in the RAM
+/// and lose them once dropped.
+///
+/// Useful for tests and ephemeral nodes.
+pub struct MemoryBlocksIndex {
+    blocks: RwLock<BTreeMap<u64, Block>>
+}
+
+impl MemoryBlocksIndex {
+    #[inline]
+    /// Create empty blocks index.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait::async_trait]
+impl BlocksIndex for MemoryBlocksIndex {
+    type Error = std::convert::Infallible;
+
+    async fn get_block(&self, number: u64) -> Result<Option<Block>, Self::Error> {
+        let blocks = self.blocks.read()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        Ok(blocks.get(&number).cloned())
+    }
+
+    async fn insert_block(&self, block: Block) -> Result<bool, Self::Error> {
+        let mut blocks = self.blocks.write()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        // Do not replace already indexed blocks.
+        if blocks.contains_key(&block.number()) {
+            return Ok(false);
+        }
+
+        blocks.insert(block.number(), block);
+
+        Ok(true)
+    }
+
+    async fn truncate_after(&self, number: u64) -> Result<Vec<Block>, Self::Error> {
+        let mut blocks = self.blocks.write()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        let Some(first_removed) = number.checked_add(1) else {
+            return Ok(vec![]);
+        };
+
+        let removed_blocks = blocks.split_off(&first_removed)
+            .into_values()
+            .collect();
+
+        Ok(removed_blocks)
+    }
+
+    async fn get_head_block(&self) -> Result<Option<Block>, Self::Error> {
+        let blocks = self.blocks.read()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        Ok(blocks.first_key_value().map(|(_, block)| block.clone()))
+    }
+
+    async fn get_tail_block(&self) -> Result<Option<Block>, Self::Error> {
+        let blocks = self.blocks.read()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        let mut blocks_iter = blocks.values();
+
+        let Some(mut tail_block) = blocks_iter.next() else {
+            return Ok(None);
+        };
+
+        // Go through the blocks while they're connected
+        // to the current tail block.
+        for block in blocks_iter {
+            if block.previous_block() != Some(tail_block.get_hash()) {
+                break;
+            }
+
+            tail_block = block;
+        }
+
+        Ok(Some(tail_block.clone()))
+    }
+
+    async fn is_empty(&self) -> Result<bool, Self::Error> {
+        let blocks = self.blocks.read()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        Ok(blocks.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hyperborealib::exports::tokio;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn index() -> Result<(), std::convert::Infallible> {
+        use hyperborealib::crypto::asymmetric::SecretKey;
+
+        use crate::block::prelude::*;
+
+        // Prepare blocks
+        let validator = SecretKey::random();
+
+        let block_a = BlockBuilder::build_root(&validator);
+        let block_b = BlockBuilder::chained(&block_a).sign(&validator);
+        let block_c = BlockBuilder::chained(&block_b).sign(&validator);
+        let block_d = BlockBuilder::chained(&block_c).sign(&validator);
+
+        // Run the tests
+        let index = MemoryBlocksIndex::new();
+
+        assert!(index.is_empty().await?);
+
+        assert!(index.get_head_block().await?.is_none());
+        assert!(index.get_tail_block().await?.is_none());
+
+        // Push A and C
+        assert!(index.insert_block(block_a.clone()).await?);
+        assert!(index.insert_block(block_c.clone()).await?);
+        assert!(!index.insert_block(block_c.clone()).await?);
+
+        assert!(!index.is_empty().await?);
+
+        assert_eq!(index.get_head_block().await?, Some(block_a.clone()));
+        assert_eq!(index.get_tail_block().await?, Some(block_a.clone()));
+
+        // Push B
+        assert!(index.insert_block(block_b.clone()).await?);
+
+        assert_eq!(index.get_head_block().await?, Some(block_a.clone()));
+        assert_eq!(index.get_tail_block().await?, Some(block_c.clone()));
+
+        // Push D
+        assert!(index.insert_block(block_d.clone()).await?);
+
+        assert_eq!(index.get_tail_block().await?, Some(block_d.clone()));
+
+        assert_eq!(index.get_next_block(&block_a).await?, Some(block_b.clone()));
+        assert_eq!(index.get_next_block(&block_c).await?, Some(block_d.clone()));
+        assert!(index.get_next_block(&block_d).await?.is_none());
+
+        // Truncate C and D
+        assert_eq!(index.truncate_after(1).await?, vec![block_c, block_d]);
+
+        assert_eq!(index.get_tail_block().await?, Some(block_b.clone()));
+
+        Ok(())
+    }
+}
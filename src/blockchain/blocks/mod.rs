@@ -1,8 +1,14 @@
 use crate::block::Block;
 
 mod chunked_blocks;
+mod memory_blocks;
+mod cached_blocks;
+mod validating_blocks;
 
 pub use chunked_blocks::*;
+pub use memory_blocks::*;
+pub use cached_blocks::*;
+pub use validating_blocks::*;
 
 #[async_trait::async_trait]
 /// This trait implementation should manage information
@@ -20,6 +26,20 @@ pub trait BlocksIndex {
     /// given block. Otherwise return `true`.
     async fn insert_block(&self, block: Block) -> Result<bool, Self::Error>;
 
+    /// Remove all the blocks with numbers higher
+    /// than the given one from the index.
+    ///
+    /// This method should return list of removed
+    /// blocks sorted in ascending order.
+    ///
+    /// ```text
+    /// [0] <- [1] <- [2] <- [3] <- [4]
+    ///               ^^^ truncate_after(2)
+    ///
+    /// [0] <- [1] <- [2]
+    /// ```
+    async fn truncate_after(&self, number: u64) -> Result<Vec<Block>, Self::Error>;
+
     /// Try to get a block next to the given one.
     ///
     /// This method should have the fastest next block lookup implementation.
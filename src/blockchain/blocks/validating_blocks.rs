@@ -0,0 +1,493 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, RwLock, PoisonError};
+
+use hyperborealib::crypto::asymmetric::PublicKey;
+use hyperborealib::exports::tokio::sync::Mutex;
+
+use crate::block::prelude::*;
+use crate::blockchain::authorities::AuthoritiesIndex;
+
+use super::*;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ValidatingBlocksIndexError<A, B> {
+    #[error("Authorities index error: {0}")]
+    AuthoritiesIndex(A),
+
+    #[error("Blocks index error: {0}")]
+    BlocksIndex(B),
+
+    #[error("Failed to validate block: {0}")]
+    BlockValidation(#[from] BlockValidationError)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlockRejectionReason {
+    /// Block with the same number is already indexed.
+    AlreadyIndexed {
+        block_number: u64
+    },
+
+    /// Invalid block validator.
+    InvalidValidator {
+        block_number: u64,
+        validator: PublicKey
+    },
+
+    /// Block is not linked to the indexed blocks
+    /// and the floating blocks area is full.
+    FloatingBlocksLimit {
+        block_number: u64
+    },
+
+    /// Block itself is invalid or doesn't
+    /// follow its parent block.
+    InvalidBlock {
+        block_number: u64,
+        reason: BlockValidationResult
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlockInsertResult {
+    /// Block is linked to the indexed blocks
+    /// and was inserted to the index.
+    Inserted,
+
+    /// Block is not linked to the indexed blocks
+    /// and was stored in the floating blocks area.
+    Floating,
+
+    /// Block was rejected.
+    Rejected(BlockRejectionReason)
+}
+
+impl BlockInsertResult {
+    #[inline]
+    pub fn is_inserted(&self) -> bool {
+        self == &Self::Inserted
+    }
+}
+
+/// Validating blocks index wrapper.
+///
+/// This struct will validate every inserted block
+/// against the tail block of the wrapped blocks index
+/// and the blockchain's authorities before indexing it.
+///
+/// Blocks which can't be linked to the indexed ones yet
+/// are kept in the floating blocks area until their
+/// ancestors are inserted. Several blocks with the same
+/// number can be kept, e.g. from different forks, and the
+/// one which follows the inserted parent is indexed.
+/// The first indexed block must be the root block.
+///
+/// ```text
+/// [0] <- [1] <- [2] <- ??? <- ... <- ??? <- [6] <- [7]
+/// ^^^^^^^^^^^^^^^^^ wrapped index           ^^^^^^^^^^ floating area
+/// ```
+pub struct ValidatingBlocksIndex<A, B> {
+    authorities_index: Arc<A>,
+    index: B,
+    floating_blocks: RwLock<BTreeMap<u64, Vec<Block>>>,
+    max_floating_blocks: usize,
+
+    /// Lock held while the wrapped index is read,
+    /// checked and updated.
+    insert_lock: Mutex<()>,
+
+    validation_context: ValidationContext
+}
+
+impl<A, B> ValidatingBlocksIndex<A, B>
+where
+    A: AuthoritiesIndex + Send + Sync,
+    B: BlocksIndex + Send + Sync
+{
+    /// Default maximal amount of floating blocks.
+    pub const DEFAULT_MAX_FLOATING_BLOCKS: usize = 1024;
+
+    #[inline]
+    /// Wrap given blocks index.
+    pub fn new(authorities_index: Arc<A>, index: B) -> Self {
+        Self {
+            authorities_index,
+            index,
+            floating_blocks: RwLock::new(BTreeMap::new()),
+            max_floating_blocks: Self::DEFAULT_MAX_FLOATING_BLOCKS,
+            insert_lock: Mutex::new(()),
+            validation_context: ValidationContext::default()
+        }
+    }
+
+    #[inline]
+    /// Change maximal amount of floating blocks.
+    ///
+    /// New floating blocks will be rejected
+    /// once this limit is reached.
+    pub fn with_max_floating_blocks(mut self, max_floating_blocks: usize) -> Self {
+        self.max_floating_blocks = max_floating_blocks;
+
+        self
+    }
+
+    #[inline]
+    /// Change context used to validate blocks.
+    pub fn with_validation_context(mut self, context: ValidationContext) -> Self {
+        self.validation_context = context;
+
+        self
+    }
+
+    #[inline]
+    /// Get reference to the wrapped blocks index.
+    pub fn inner(&self) -> &B {
+        &self.index
+    }
+
+    /// Get list of floating blocks sorted in ascending order.
+    pub fn floating_blocks(&self) -> Vec<Block> {
+        self.floating_blocks.read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .values()
+            .flatten()
+            .cloned()
+            .collect()
+    }
+
+    /// Verify that the block follows the given one.
+    fn check_link(previous: &Block, block: &Block) -> Option<BlockRejectionReason> {
+        block.check_parent(previous)
+            .map(|reason| BlockRejectionReason::InvalidBlock {
+                block_number: block.number(),
+                reason
+            })
+    }
+
+    /// Try to insert a block to the index,
+    /// returning the reason if it was rejected.
+    pub async fn try_insert_block(&self, block: Block) -> Result<BlockInsertResult, ValidatingBlocksIndexError<A::Error, B::Error>> {
+        // Validate block's signer.
+        let is_authority = self.authorities_index.is_authority(block.validator()).await
+            .map_err(ValidatingBlocksIndexError::AuthoritiesIndex)?;
+
+        if !is_authority {
+            return Ok(BlockInsertResult::Rejected(BlockRejectionReason::InvalidValidator {
+                block_number: block.number(),
+                validator: block.validator().clone()
+            }));
+        }
+
+        // Validate block's content.
+        let reason = block.validate_with(&self.validation_context)?;
+
+        if !reason.is_valid() {
+            return Ok(BlockInsertResult::Rejected(BlockRejectionReason::InvalidBlock {
+                block_number: block.number(),
+                reason
+            }));
+        }
+
+        // Prevent concurrent insertions from linking
+        // blocks to the outdated tail or head block.
+        let _lock = self.insert_lock.lock().await;
+
+        let tail_block = self.index.get_tail_block().await
+            .map_err(ValidatingBlocksIndexError::BlocksIndex)?;
+
+        // Insert the root block to the empty index or keep
+        // the block as floating until the root is inserted.
+        let Some(tail_block) = tail_block else {
+            if block.previous_block().is_some() {
+                return Ok(self.insert_floating_block(block));
+            }
+
+            return self.insert_linked_block(block).await;
+        };
+
+        // Insert the block if it follows the tail block.
+        if block.number() == tail_block.number() + 1 {
+            if let Some(reason) = Self::check_link(&tail_block, &block) {
+                return Ok(BlockInsertResult::Rejected(reason));
+            }
+
+            return self.insert_linked_block(block).await;
+        }
+
+        // Keep the block as floating if it's newer than the tail.
+        if block.number() > tail_block.number() {
+            return Ok(self.insert_floating_block(block));
+        }
+
+        let head_block = self.index.get_head_block().await
+            .map_err(ValidatingBlocksIndexError::BlocksIndex)?;
+
+        match head_block {
+            // Insert the block if it precedes the head block.
+            Some(head_block) if block.number() + 1 == head_block.number() => {
+                if let Some(reason) = Self::check_link(&block, &head_block) {
+                    return Ok(BlockInsertResult::Rejected(reason));
+                }
+
+                self.insert_linked_block(block).await
+            }
+
+            // Keep the block as floating if it's older than the head.
+            Some(head_block) if block.number() < head_block.number() => {
+                Ok(self.insert_floating_block(block))
+            }
+
+            _ => Ok(BlockInsertResult::Rejected(BlockRejectionReason::AlreadyIndexed {
+                block_number: block.number()
+            }))
+        }
+    }
+
+    /// Insert already validated block to the wrapped index
+    /// and try to link floating blocks to it.
+    async fn insert_linked_block(&self, block: Block) -> Result<BlockInsertResult, ValidatingBlocksIndexError<A::Error, B::Error>> {
+        let mut tail_block = block.clone();
+
+        let inserted = self.index.insert_block(block).await
+            .map_err(ValidatingBlocksIndexError::BlocksIndex)?;
+
+        if !inserted {
+            return Ok(BlockInsertResult::Rejected(BlockRejectionReason::AlreadyIndexed {
+                block_number: tail_block.number()
+            }));
+        }
+
+        // Insert floating blocks which follow the new one.
+        loop {
+            let candidates = self.floating_blocks.write()
+                .unwrap_or_else(PoisonError::into_inner)
+                .remove(&(tail_block.number() + 1));
+
+            let Some(candidates) = candidates else {
+                break;
+            };
+
+            // Drop floating blocks which are not linked,
+            // e.g. the ones from other forks.
+            let next_block = candidates.into_iter()
+                .find(|candidate| Self::check_link(&tail_block, candidate).is_none());
+
+            let Some(next_block) = next_block else {
+                break;
+            };
+
+            let inserted = self.index.insert_block(next_block.clone()).await
+                .map_err(ValidatingBlocksIndexError::BlocksIndex)?;
+
+            if !inserted {
+                break;
+            }
+
+            tail_block = next_block;
+        }
+
+        self.discard_stale_floating_blocks().await?;
+
+        Ok(BlockInsertResult::Inserted)
+    }
+
+    /// Remove floating blocks which numbers
+    /// are already taken by the indexed blocks.
+    async fn discard_stale_floating_blocks(&self) -> Result<(), ValidatingBlocksIndexError<A::Error, B::Error>> {
+        let head_block = self.index.get_head_block().await
+            .map_err(ValidatingBlocksIndexError::BlocksIndex)?;
+
+        let tail_block = self.index.get_tail_block().await
+            .map_err(ValidatingBlocksIndexError::BlocksIndex)?;
+
+        if let (Some(head_block), Some(tail_block)) = (head_block, tail_block) {
+            self.floating_blocks.write()
+                .unwrap_or_else(PoisonError::into_inner)
+                .retain(|number, _| *number < head_block.number() || *number > tail_block.number());
+        }
+
+        Ok(())
+    }
+
+    /// Store block in the floating blocks area.
+    fn insert_floating_block(&self, block: Block) -> BlockInsertResult {
+        let mut floating_blocks = self.floating_blocks.write()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        let is_stored = floating_blocks.get(&block.number())
+            .map(|candidates| candidates.contains(&block))
+            .unwrap_or(false);
+
+        if is_stored {
+            return BlockInsertResult::Rejected(BlockRejectionReason::AlreadyIndexed {
+                block_number: block.number()
+            });
+        }
+
+        let total_blocks = floating_blocks.values()
+            .map(Vec::len)
+            .sum::<usize>();
+
+        if total_blocks >= self.max_floating_blocks {
+            return BlockInsertResult::Rejected(BlockRejectionReason::FloatingBlocksLimit {
+                block_number: block.number()
+            });
+        }
+
+        floating_blocks.entry(block.number())
+            .or_default()
+            .push(block);
+
+        BlockInsertResult::Floating
+    }
+}
+
+#[async_trait::async_trait]
+impl<A, B> BlocksIndex for ValidatingBlocksIndex<A, B>
+where
+    A: AuthoritiesIndex + Send + Sync,
+    B: BlocksIndex + Send + Sync
+{
+    type Error = ValidatingBlocksIndexError<A::Error, B::Error>;
+
+    #[inline]
+    async fn get_block(&self, number: u64) -> Result<Option<Block>, Self::Error> {
+        self.index.get_block(number).await
+            .map_err(ValidatingBlocksIndexError::BlocksIndex)
+    }
+
+    #[inline]
+    async fn insert_block(&self, block: Block) -> Result<bool, Self::Error> {
+        Ok(self.try_insert_block(block).await?.is_inserted())
+    }
+
+    async fn truncate_after(&self, number: u64) -> Result<Vec<Block>, Self::Error> {
+        let _lock = self.insert_lock.lock().await;
+
+        self.index.truncate_after(number).await
+            .map_err(ValidatingBlocksIndexError::BlocksIndex)
+    }
+
+    #[inline]
+    async fn get_next_block(&self, block: &Block) -> Result<Option<Block>, Self::Error> {
+        self.index.get_next_block(block).await
+            .map_err(ValidatingBlocksIndexError::BlocksIndex)
+    }
+
+    #[inline]
+    async fn get_head_block(&self) -> Result<Option<Block>, Self::Error> {
+        self.index.get_head_block().await
+            .map_err(ValidatingBlocksIndexError::BlocksIndex)
+    }
+
+    #[inline]
+    async fn get_tail_block(&self) -> Result<Option<Block>, Self::Error> {
+        self.index.get_tail_block().await
+            .map_err(ValidatingBlocksIndexError::BlocksIndex)
+    }
+
+    #[inline]
+    async fn is_empty(&self) -> Result<bool, Self::Error> {
+        self.index.is_empty().await
+            .map_err(ValidatingBlocksIndexError::BlocksIndex)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hyperborealib::exports::tokio;
+    use hyperborealib::crypto::asymmetric::SecretKey;
+
+    use crate::blockchain::authorities::MemoryAuthorities;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn index() -> Result<(), ValidatingBlocksIndexError<std::convert::Infallible, std::convert::Infallible>> {
+        let authority = SecretKey::random();
+        let stranger = SecretKey::random();
+
+        // Prepare blocks
+        let block_a = BlockBuilder::build_root(&authority);
+        let block_b = BlockBuilder::chained(&block_a).sign(&authority);
+        let block_c = BlockBuilder::chained(&block_b).sign(&authority);
+        let block_d = BlockBuilder::chained(&block_c).sign(&authority);
+
+        let block_b_stranger = BlockBuilder::chained(&block_a).sign(&stranger);
+
+        let block_b_unlinked = BlockBuilder::new()
+            .with_previous(Hash::MAX)
+            .with_number(1u64)
+            .sign(&authority);
+
+        // Run the tests
+        let index = ValidatingBlocksIndex::new(
+            Arc::new(MemoryAuthorities::from_iter([authority.public_key()])),
+            MemoryBlocksIndex::new()
+        );
+
+        // Push B before the root block
+        assert_eq!(index.try_insert_block(block_b.clone()).await?, BlockInsertResult::Floating);
+        assert!(index.is_empty().await?);
+
+        // Push A
+        assert_eq!(index.try_insert_block(block_a.clone()).await?, BlockInsertResult::Inserted);
+        assert_eq!(index.try_insert_block(block_a.clone()).await?, BlockInsertResult::Rejected(BlockRejectionReason::AlreadyIndexed {
+            block_number: 0
+        }));
+
+        // B is linked to A
+        assert_eq!(index.get_tail_block().await?, Some(block_b.clone()));
+        assert!(index.floating_blocks().is_empty());
+
+        index.truncate_after(0).await?;
+
+        // Push invalid B
+        assert_eq!(index.try_insert_block(block_b_stranger).await?, BlockInsertResult::Rejected(BlockRejectionReason::InvalidValidator {
+            block_number: 1,
+            validator: stranger.public_key()
+        }));
+
+        assert_eq!(index.try_insert_block(block_b_unlinked).await?, BlockInsertResult::Rejected(BlockRejectionReason::InvalidBlock {
+            block_number: 1,
+            reason: BlockValidationResult::InvalidParentReference {
+                expected: block_a.get_hash(),
+                got: Some(Hash::MAX)
+            }
+        }));
+
+        // Push C and D
+        assert_eq!(index.try_insert_block(block_c.clone()).await?, BlockInsertResult::Floating);
+        assert_eq!(index.try_insert_block(block_d.clone()).await?, BlockInsertResult::Floating);
+
+        assert_eq!(index.floating_blocks(), vec![block_c.clone(), block_d.clone()]);
+        assert_eq!(index.get_tail_block().await?, Some(block_a.clone()));
+
+        // Push B
+        assert_eq!(index.try_insert_block(block_b.clone()).await?, BlockInsertResult::Inserted);
+
+        assert!(index.floating_blocks().is_empty());
+        assert_eq!(index.get_tail_block().await?, Some(block_d.clone()));
+
+        index.truncate_after(0).await?;
+
+        // Push C from another fork before the real C
+        let block_b_fork = BlockBuilder::chained(&block_a).sign(&authority);
+        let block_c_fork = BlockBuilder::chained(&block_b_fork).sign(&authority);
+
+        assert_eq!(index.try_insert_block(block_c_fork.clone()).await?, BlockInsertResult::Floating);
+        assert_eq!(index.try_insert_block(block_c.clone()).await?, BlockInsertResult::Floating);
+
+        assert_eq!(index.try_insert_block(block_c.clone()).await?, BlockInsertResult::Rejected(BlockRejectionReason::AlreadyIndexed {
+            block_number: 2
+        }));
+
+        // Real C follows B
+        assert_eq!(index.try_insert_block(block_b.clone()).await?, BlockInsertResult::Inserted);
+
+        assert!(index.floating_blocks().is_empty());
+        assert_eq!(index.get_tail_block().await?, Some(block_c.clone()));
+
+        Ok(())
+    }
+}
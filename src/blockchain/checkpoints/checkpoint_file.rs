@@ -0,0 +1,108 @@
+use std::path::PathBuf;
+
+use hyperborealib::exports::tokio;
+
+use super::*;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ValidationCheckpointFileError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error(transparent)]
+    Json(#[from] AsJsonError),
+
+    #[error(transparent)]
+    Serialize(#[from] serde_json::Error)
+}
+
+/// Basic validation checkpoint store implementation.
+///
+/// This struct will store the checkpoint
+/// in a single JSON file. The file is replaced
+/// atomically so a crash during the write
+/// doesn't corrupt the stored checkpoint.
+pub struct ValidationCheckpointFile {
+    path: PathBuf
+}
+
+impl ValidationCheckpointFile {
+    /// Open or create validation checkpoint file.
+    pub async fn open(path: impl Into<PathBuf>) -> std::io::Result<Self> {
+        let path: PathBuf = path.into();
+
+        if let Some(parent) = path.parent() {
+            if !parent.exists() {
+                tokio::fs::create_dir_all(parent).await?;
+            }
+        }
+
+        Ok(Self {
+            path
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl ValidationCheckpointStore for ValidationCheckpointFile {
+    type Error = ValidationCheckpointFileError;
+
+    async fn get_checkpoint(&self) -> Result<Option<ValidationCheckpoint>, Self::Error> {
+        if !self.path.exists() {
+            return Ok(None);
+        }
+
+        let checkpoint = tokio::fs::read(&self.path).await?;
+        let checkpoint = serde_json::from_slice::<Json>(&checkpoint)?;
+
+        Ok(Some(ValidationCheckpoint::from_json(&checkpoint)?))
+    }
+
+    async fn set_checkpoint(&self, checkpoint: ValidationCheckpoint) -> Result<(), Self::Error> {
+        crate::utils::write_atomic(&self.path, serde_json::to_string_pretty(&checkpoint.to_json()?)?).await?;
+
+        Ok(())
+    }
+
+    async fn clear_checkpoint(&self) -> Result<(), Self::Error> {
+        if self.path.exists() {
+            tokio::fs::remove_file(&self.path).await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::block::builder::tests::get_chained;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn store() -> Result<(), ValidationCheckpointFileError> {
+        let dir = crate::utils::test_dir("validation-checkpoint-file-test")?;
+
+        let (root, chained, _) = get_chained();
+
+        let store = ValidationCheckpointFile::open(dir.join("checkpoint.json")).await?;
+
+        assert!(store.get_checkpoint().await?.is_none());
+
+        store.set_checkpoint(ValidationCheckpoint::from(&root)).await?;
+
+        assert_eq!(store.get_checkpoint().await?, Some(ValidationCheckpoint::from(&root)));
+
+        store.set_checkpoint(ValidationCheckpoint::from(&chained)).await?;
+
+        assert_eq!(store.get_checkpoint().await?, Some(ValidationCheckpoint::from(&chained)));
+
+        store.clear_checkpoint().await?;
+
+        assert!(store.get_checkpoint().await?.is_none());
+
+        tokio::fs::remove_dir_all(&dir).await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,43 @@
+use std::sync::{RwLock, PoisonError};
+
+use super::*;
+
+#[derive(Default, Debug)]
+/// In-memory validation checkpoint store.
+///
+/// Useful for tests and ephemeral nodes.
+pub struct MemoryValidationCheckpoint {
+    checkpoint: RwLock<Option<ValidationCheckpoint>>
+}
+
+impl MemoryValidationCheckpoint {
+    #[inline]
+    /// Create empty validation checkpoint store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait::async_trait]
+impl ValidationCheckpointStore for MemoryValidationCheckpoint {
+    type Error = std::convert::Infallible;
+
+    async fn get_checkpoint(&self) -> Result<Option<ValidationCheckpoint>, Self::Error> {
+        let checkpoint = self.checkpoint.read()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        Ok(*checkpoint)
+    }
+
+    async fn set_checkpoint(&self, checkpoint: ValidationCheckpoint) -> Result<(), Self::Error> {
+        *self.checkpoint.write().unwrap_or_else(PoisonError::into_inner) = Some(checkpoint);
+
+        Ok(())
+    }
+
+    async fn clear_checkpoint(&self) -> Result<(), Self::Error> {
+        *self.checkpoint.write().unwrap_or_else(PoisonError::into_inner) = None;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,107 @@
+use serde::{Serialize, Deserialize};
+use serde_json::{json, Value as Json};
+
+use hyperborealib::rest_api::{
+    AsJson,
+    AsJsonError
+};
+
+use crate::block::prelude::*;
+
+mod checkpoint_file;
+mod memory_checkpoint;
+
+pub use checkpoint_file::*;
+pub use memory_checkpoint::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+/// Information about the latest fully validated block.
+pub struct ValidationCheckpoint {
+    pub block_number: u64,
+    pub block_hash: Hash
+}
+
+impl From<&Block> for ValidationCheckpoint {
+    #[inline]
+    fn from(block: &Block) -> Self {
+        Self {
+            block_number: block.number(),
+            block_hash: block.get_hash()
+        }
+    }
+}
+
+impl AsJson for ValidationCheckpoint {
+    fn to_json(&self) -> Result<Json, AsJsonError> {
+        Ok(json!({
+            "format": 1,
+            "checkpoint": {
+                "number": self.block_number,
+                "hash": self.block_hash.to_base64()
+            }
+        }))
+    }
+
+    fn from_json(json: &Json) -> Result<Self, AsJsonError> where Self: Sized {
+        let Some(format) = json.get("format").and_then(Json::as_u64) else {
+            return Err(AsJsonError::FieldNotFound("format"));
+        };
+
+        match format {
+            1 => {
+                let Some(checkpoint) = json.get("checkpoint") else {
+                    return Err(AsJsonError::FieldNotFound("checkpoint"));
+                };
+
+                Ok(Self {
+                    block_number: checkpoint.get("number")
+                        .and_then(Json::as_u64)
+                        .ok_or_else(|| AsJsonError::FieldValueInvalid("checkpoint.number"))?,
+
+                    block_hash: checkpoint.get("hash")
+                        .and_then(Json::as_str)
+                        .map(Hash::from_base64)
+                        .ok_or_else(|| AsJsonError::FieldValueInvalid("checkpoint.hash"))?
+                        .map_err(|err| AsJsonError::Other(err.into()))?
+                })
+            }
+
+            version => Err(AsJsonError::InvalidStandard(version))
+        }
+    }
+}
+
+#[async_trait::async_trait]
+/// This trait implementation should store information
+/// about the latest fully validated block of the blockchain.
+///
+/// It is used by `Blockchain::validate_incremental`
+/// to not to validate the whole blockchain every time.
+pub trait ValidationCheckpointStore {
+    type Error: std::error::Error + Send + Sync;
+
+    /// Get stored validation checkpoint.
+    async fn get_checkpoint(&self) -> Result<Option<ValidationCheckpoint>, Self::Error>;
+
+    /// Replace stored validation checkpoint.
+    async fn set_checkpoint(&self, checkpoint: ValidationCheckpoint) -> Result<(), Self::Error>;
+
+    /// Remove stored validation checkpoint.
+    async fn clear_checkpoint(&self) -> Result<(), Self::Error>;
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::block::builder::tests::get_root;
+
+    use super::*;
+
+    #[test]
+    fn serialize() -> Result<(), AsJsonError> {
+        let checkpoint = ValidationCheckpoint::from(&get_root().0);
+
+        assert_eq!(ValidationCheckpoint::from_json(&checkpoint.to_json()?)?, checkpoint);
+
+        Ok(())
+    }
+}
@@ -1,18 +1,24 @@
 use std::sync::Arc;
+use std::collections::{HashMap, HashSet};
+
+use serde::Serialize;
 
 use hyperborealib::crypto::asymmetric::PublicKey;
-use hyperborealib::time::timestamp;
 
 use crate::block::prelude::*;
+use crate::block::BlockLink;
 
 pub mod authorities;
 pub mod blocks;
 pub mod transactions;
+pub mod checkpoints;
+pub mod parallel;
 pub mod basic_blockchain;
 
 pub mod prelude {
     pub use super::{
         BlockchainValidationError,
+        IncrementalValidationError,
         BlockchainValidationResult,
         Blockchain
     };
@@ -20,24 +26,281 @@ pub mod prelude {
     pub use super::authorities::*;
     pub use super::blocks::*;
     pub use super::transactions::*;
+    pub use super::checkpoints::*;
+    pub use super::parallel::*;
     pub use super::basic_blockchain::*;
 }
 
 use prelude::*;
 
 #[derive(Debug, thiserror::Error)]
-pub enum BlockchainValidationError<A, B> {
+pub enum BlockchainValidationError<A, B, T> {
     #[error("Authorities index error: {0}")]
     AuthoritiesIndex(A),
 
     #[error("Blocks index error: {0}")]
     BlocksIndex(B),
 
+    #[error("Transactions index error: {0}")]
+    TransactionsIndex(T),
+
     #[error("Failed to validate block: {0}")]
-    BlockValidation(#[from] BlockValidationError)
+    BlockValidation(#[from] BlockValidationError),
+
+    #[error("Validation worker failed: {0}")]
+    ValidationWorker(#[from] hyperborealib::exports::tokio::task::JoinError)
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum IncrementalValidationError<A, B, T, C> {
+    #[error(transparent)]
+    Validation(#[from] BlockchainValidationError<A, B, T>),
+
+    #[error("Validation checkpoint store error: {0}")]
+    CheckpointStore(C)
+}
+
+/// Sequential blockchain structure rules.
+pub(crate) struct ChainLinkValidator {
+    /// Previous block, `None` if unknown.
+    previous: Option<BlockLink>,
+
+    /// Maximum allowed timestamp.
+    max_timestamp: u64,
+
+    /// Blocks numbers of the validated transactions
+    /// and of the earlier ones loaded from the index.
+    transactions: HashMap<Hash, u64>,
+
+    /// Whether transactions' nonces should be verified.
+    require_nonces: bool,
+
+    /// Latest nonces of the validated transactions' authors
+    /// and of the earlier ones loaded from the index.
+    nonces: HashMap<PublicKey, u64>,
+
+    /// Whether transactions' references should be verified.
+    require_references: bool,
+
+    /// Whether validation starts from the root block
+    /// so all the previous transactions are known.
+    complete_history: bool
+}
+
+impl ChainLinkValidator {
+    /// Prepare rules for the blocks starting from the given one.
+    ///
+    /// If the previous block is given then the first
+    /// block will be verified to follow it.
+    pub fn new(context: &ValidationContext, first_block: Option<&Block>, previous: Option<&Block>) -> Self {
+        Self {
+            previous: previous.map(BlockLink::from),
+
+            max_timestamp: context.max_timestamp(),
+
+            transactions: HashMap::new(),
+
+            require_nonces: context.require_nonces,
+            nonces: HashMap::new(),
+
+            require_references: context.require_references,
+
+            complete_history: previous.is_none() && first_block
+                .map(|block| block.previous_block.is_none())
+                .unwrap_or(true)
+        }
+    }
+
+    /// Remember the block's transactions, referenced
+    /// transactions and its authors' nonces which were
+    /// included before the first validated block.
+    ///
+    /// This is needed to find duplicates of the earlier
+    /// transactions and to verify nonces and references
+    /// when validation doesn't start from the root block.
+    pub async fn load_history<T>(&mut self, transactions: &T, block: &Block) -> Result<(), T::Error>
+    where T: TransactionsIndex + Send + Sync
+    {
+        if self.complete_history {
+            return Ok(());
+        }
+
+        for transaction in &block.transactions {
+            if self.transactions.contains_key(&transaction.hash) {
+                continue;
+            }
+
+            // Validated blocks are indexed as well
+            // so only the earlier ones are remembered.
+            if let Some(first_block) = transactions.get_transaction_block(&transaction.hash).await? {
+                if first_block < block.number {
+                    self.transactions.insert(transaction.hash, first_block);
+                }
+            }
+        }
+
+        if self.require_nonces {
+            for transaction in &block.transactions {
+                if transaction.nonce.is_none() || self.nonces.contains_key(&transaction.author) {
+                    continue;
+                }
+
+                if let Some(nonce) = transactions.get_latest_nonce_before(&transaction.author, block.number).await? {
+                    self.nonces.insert(transaction.author.clone(), nonce);
+                }
+            }
+        }
+
+        if self.require_references {
+            for reference in block.transactions.iter().flat_map(|transaction| &transaction.references) {
+                if self.transactions.contains_key(reference) {
+                    continue;
+                }
+
+                if let Some(first_block) = transactions.get_transaction_block(reference).await? {
+                    if first_block < block.number {
+                        self.transactions.insert(*reference, first_block);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Verify block's timestamp, number and previous hash reference.
+    pub fn check(&self, block: &Block) -> Option<BlockchainValidationResult> {
+        self.check_all(block).into_iter().next()
+    }
+
+    /// Verify block's timestamp, number and previous hash reference,
+    /// returning all the violated rules.
+    pub fn check_all(&self, block: &Block) -> Vec<BlockchainValidationResult> {
+        // Validate block's number, previous hash reference
+        // and timestamp. The first block is accepted as is
+        // if its previous block is unknown.
+        let mut results = self.previous.map(|previous| previous.check_all(block))
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|result| BlockchainValidationResult::from_parent_issue(block.number, result))
+            .collect::<Vec<_>>();
+
+        // Validate that block is not created in the future
+        let invalid_time = results.iter()
+            .any(|result| matches!(result, BlockchainValidationResult::InvalidCreationTime { .. }));
+
+        if !invalid_time && block.created_at > self.max_timestamp {
+            results.push(BlockchainValidationResult::InvalidCreationTime {
+                block_number: block.number,
+                created_at: block.created_at
+            });
+        }
+
+        // Validate that block's transactions
+        // were not included before
+        for transaction in &block.transactions {
+            if let Some(first_block) = self.transactions.get(&transaction.hash) {
+                results.push(BlockchainValidationResult::DuplicateTransaction {
+                    transaction: transaction.hash,
+                    first_block: *first_block,
+                    duplicate_block: block.number
+                });
+            }
+        }
+
+        // Validate that authors' nonces start from zero
+        // and are increasing by one.
+        if self.require_nonces {
+            let mut nonces = HashMap::new();
+
+            for transaction in &block.transactions {
+                let Some(nonce) = transaction.nonce else {
+                    continue;
+                };
+
+                let latest = nonces.get(&transaction.author)
+                    .or_else(|| self.nonces.get(&transaction.author))
+                    .copied();
+
+                let expected = match latest {
+                    Some(latest) => latest.checked_add(1),
+                    None => Some(0)
+                };
+
+                if expected != Some(nonce) {
+                    results.push(BlockchainValidationResult::InvalidNonce {
+                        block_number: block.number,
+                        transaction: transaction.hash,
+                        expected: latest.map(|latest| latest.saturating_add(1)).unwrap_or(0),
+                        got: nonce
+                    });
+                }
+
+                nonces.insert(&transaction.author, nonce);
+            }
+        }
+
+        // Validate that referenced transactions were included
+        // before. Transactions of the blocks before the validated
+        // ones must be loaded from the index using `load_history`.
+        if self.require_references {
+            let mut block_transactions = HashSet::new();
+
+            for transaction in &block.transactions {
+                for reference in &transaction.references {
+                    if !self.transactions.contains_key(reference) && !block_transactions.contains(reference) {
+                        results.push(BlockchainValidationResult::UnknownReference {
+                            block_number: block.number,
+                            transaction: transaction.hash,
+                            reference: *reference
+                        });
+                    }
+                }
+
+                block_transactions.insert(transaction.hash);
+            }
+        }
+
+        results
+    }
+
+    /// Convert block's validation result.
+    pub fn check_sign(block: &Block, result: Result<BlockValidationResult, BlockValidationError>) -> Option<BlockchainValidationResult> {
+        match result {
+            Ok(reason) if !reason.is_valid() => Some(BlockchainValidationResult::InvalidSign {
+                block_number: block.number,
+                validator: block.validator.clone(),
+                sign: block.sign.clone(),
+                reason
+            }),
+
+            Err(err) => Some(BlockchainValidationResult::SignVerificationError {
+                block_number: block.number,
+                validator: block.validator.clone(),
+                sign: block.sign.clone(),
+                reason: err.into()
+            }),
+
+            _ => None
+        }
+    }
+
+    /// Remember given block as the previous one.
+    pub fn push(&mut self, block: &Block) {
+        self.previous = Some(BlockLink::from(block));
+
+        for transaction in &block.transactions {
+            self.transactions.entry(transaction.hash)
+                .or_insert(block.number);
+
+            if let Some(nonce) = transaction.nonce {
+                self.nonces.insert(transaction.author.clone(), nonce);
+            }
+        }
+    }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub enum BlockchainValidationResult {
     /// Unknown block hash.
     UnknownBlockHash(Hash),
@@ -83,15 +346,72 @@ pub enum BlockchainValidationResult {
         block_number: u64,
         validator: PublicKey,
         sign: Vec<u8>,
+        reason: SharedError<BlockValidationError>
+    },
 
-        // TODO: BlockValidationError
-        reason: String
+    /// Transaction was already included
+    /// in a previous block.
+    DuplicateTransaction {
+        transaction: Hash,
+        first_block: u64,
+        duplicate_block: u64
+    },
+
+    /// Transaction's nonce doesn't follow the
+    /// previous nonce of its author.
+    InvalidNonce {
+        block_number: u64,
+        transaction: Hash,
+        expected: u64,
+        got: u64
+    },
+
+    /// Transaction refers to a transaction which
+    /// was not included in the previous blocks.
+    UnknownReference {
+        block_number: u64,
+        transaction: Hash,
+        reference: Hash
+    },
+
+    /// Checkpointed block was changed
+    /// since the last validation.
+    CheckpointMismatch {
+        block_number: u64,
+        expected_hash: Hash,
+        got_hash: Option<Hash>
     },
 
     /// Blockchain is valid.
     Valid
 }
 
+impl BlockchainValidationResult {
+    /// Convert block's parent linkage issue
+    /// returned by `Block::check_parent`.
+    pub(crate) fn from_parent_issue(block_number: u64, issue: BlockValidationResult) -> Option<Self> {
+        match issue {
+            BlockValidationResult::InvalidParentNumber { parent_number, .. } => Some(Self::InvalidNumber {
+                block_number,
+                previous_number: parent_number
+            }),
+
+            BlockValidationResult::InvalidParentReference { expected, got } => Some(Self::InvalidPreviosBlockReference {
+                block_number,
+                expected_previous: Some(expected),
+                got_previous: got
+            }),
+
+            BlockValidationResult::InvalidParentCreationTime { created_at, .. } => Some(Self::InvalidCreationTime {
+                block_number,
+                created_at
+            }),
+
+            _ => None
+        }
+    }
+}
+
 #[async_trait::async_trait]
 pub trait Blockchain {
     type AuthoritiesIndex: AuthoritiesIndex + Send + Sync;
@@ -106,6 +426,18 @@ pub trait Blockchain {
     fn blocks_index_ref(&self) -> &Self::BlocksIndex;
     fn transactions_index_ref(&self) -> &Self::TransactionsIndex;
 
+    /// Context used to validate blockchain's
+    /// blocks and transactions.
+    fn validation_context(&self) -> ValidationContext {
+        ValidationContext::default()
+    }
+
+    #[inline]
+    /// Identifier of the blockchain.
+    fn chain_id(&self) -> Option<ChainId> {
+        self.validation_context().chain_id
+    }
+
     /// Validate blockchain structure.
     ///
     /// This method will:
@@ -117,9 +449,16 @@ pub trait Blockchain {
     ///    order with a one step.
     ///
     /// 3. Verify that each block is signed by the blockchain's
-    ///    authority.
+    ///    authority or by its delegated session key.
     ///
-    /// 4. Validate blocks consistency.
+    /// 4. Verify that each transaction is included only once,
+    ///    that authors' nonces start from zero and have no gaps
+    ///    or duplicates and that transactions' references point
+    ///    to previously included transactions if they're required.
+    ///    Transactions of the blocks before the validated ones
+    ///    are searched in the transactions index.
+    ///
+    /// 5. Validate blocks consistency.
     ///
     /// Since this method is resource heavy it's recommended
     /// to run it with `since_block` property and cache
@@ -128,7 +467,8 @@ pub trait Blockchain {
         BlockchainValidationResult,
         BlockchainValidationError<
             <Self::AuthoritiesIndex as AuthoritiesIndex>::Error,
-            <Self::BlocksIndex as BlocksIndex>::Error
+            <Self::BlocksIndex as BlocksIndex>::Error,
+            <Self::TransactionsIndex as TransactionsIndex>::Error
         >
     > {
         self.validate_since(0).await
@@ -140,14 +480,14 @@ pub trait Blockchain {
         BlockchainValidationResult,
         BlockchainValidationError<
             <Self::AuthoritiesIndex as AuthoritiesIndex>::Error,
-            <Self::BlocksIndex as BlocksIndex>::Error
+            <Self::BlocksIndex as BlocksIndex>::Error,
+            <Self::TransactionsIndex as TransactionsIndex>::Error
         >
     > {
-        let authorities = self.authorities_index();
         let blocks = self.blocks_index();
 
         // Get initial block
-        let mut block = if start_block_number > 0 {
+        let block = if start_block_number > 0 {
             blocks.get_block(start_block_number).await
                 .map_err(BlockchainValidationError::BlocksIndex)?
         } else {
@@ -155,88 +495,254 @@ pub trait Blockchain {
                 .map_err(BlockchainValidationError::BlocksIndex)?
         };
 
-        // Maximum allowed timestamp (+24h just in case)
-        let max_timestamp = timestamp() + 24 * 60 * 60;
+        let (result, _) = self.validate_blocks(block, None).await?;
+
+        Ok(result)
+    }
+
+    /// Validate blockchain structure starting
+    /// from the block with a given number,
+    /// collecting all the found issues.
+    ///
+    /// Unlike `validate_since` this method will not
+    /// stop on the first found problem.
+    async fn validate_since_report(&self, start_block_number: u64) -> Result<
+        ValidationReport,
+        BlockchainValidationError<
+            <Self::AuthoritiesIndex as AuthoritiesIndex>::Error,
+            <Self::BlocksIndex as BlocksIndex>::Error,
+            <Self::TransactionsIndex as TransactionsIndex>::Error
+        >
+    > {
+        let authorities = self.authorities_index();
+        let blocks = self.blocks_index();
+        let transactions = self.transactions_index();
 
-        // Previous block's hash
-        let mut prev_block_hash = block.as_ref()
-            .and_then(|block| block.previous_block);
+        let context = self.validation_context();
 
-        // Previous block's creation timestamp
-        let mut prev_created_at = 0;
+        let mut report = ValidationReport::new();
 
-        let mut prev_number = if start_block_number > 0 {
-            start_block_number - 1
+        // Get initial block
+        let mut block = if start_block_number > 0 {
+            blocks.get_block(start_block_number).await
+                .map_err(BlockchainValidationError::BlocksIndex)?
         } else {
-            0
+            let head_block = blocks.get_head_block().await
+                .map_err(BlockchainValidationError::BlocksIndex)?;
+
+            // Warn about truncated blockchain
+            if let Some(previous_block) = head_block.as_ref().and_then(|block| block.previous_block) {
+                report.push_warning(head_block.as_ref().map(|block| block.number), None, ValidationIssue::Truncated {
+                    previous_block
+                });
+            }
+
+            head_block
         };
 
+        let mut links = ChainLinkValidator::new(&context, block.as_ref(), None);
+
         // Validate all the blocks
         while let Some(curr_block) = block.take() {
-            // Validate block's timestamp
-            if curr_block.created_at < prev_created_at || curr_block.created_at > max_timestamp {
-                return Ok(BlockchainValidationResult::InvalidCreationTime {
-                    block_number: curr_block.number,
-                    created_at: curr_block.created_at
-                });
+            let block_number = Some(curr_block.number);
+
+            links.load_history(transactions.as_ref(), &curr_block).await
+                .map_err(BlockchainValidationError::TransactionsIndex)?;
+
+            // Validate block's timestamp, number and previous hash reference
+            for result in links.check_all(&curr_block) {
+                report.push_error(block_number, None, ValidationIssue::Blockchain(result));
             }
 
-            // Validate block's number
-            if prev_number > 0 && prev_number + 1 != curr_block.number {
-                return Ok(BlockchainValidationResult::InvalidNumber {
+            // Validate block's signer
+            let is_authority = authorities.is_authority(curr_block.authority()).await
+                .map_err(BlockchainValidationError::AuthoritiesIndex)?;
+
+            if !is_authority {
+                report.push_error(block_number, None, ValidationIssue::Blockchain(BlockchainValidationResult::InvalidValidator {
                     block_number: curr_block.number,
-                    previous_number: prev_number
-                });
+                    validator: curr_block.authority().clone()
+                }));
             }
 
-            // Validate block's previous hash reference
-            if prev_block_hash != curr_block.previous_block {
-                return Ok(BlockchainValidationResult::InvalidPreviosBlockReference {
-                    block_number: curr_block.number,
-                    expected_previous: prev_block_hash,
-                    got_previous: curr_block.previous_block
-                });
+            // Validate block itself
+            curr_block.report_issues(&context, &mut report);
+
+            links.push(&curr_block);
+
+            block = blocks.get_next_block(&curr_block).await
+                .map_err(BlockchainValidationError::BlocksIndex)?;
+        }
+
+        Ok(report)
+    }
+
+    /// Validate blockchain structure starting
+    /// from the block with a given number,
+    /// verifying blocks' signatures in parallel.
+    ///
+    /// Blocks are fetched and verified to follow each other
+    /// sequentially while their signatures are verified
+    /// in batches by a pool of workers. Given callback is
+    /// called every time a batch of blocks is validated.
+    async fn validate_parallel<F>(&self, start_block_number: u64, options: ParallelValidationOptions, progress: F) -> Result<
+        BlockchainValidationResult,
+        BlockchainValidationError<
+            <Self::AuthoritiesIndex as AuthoritiesIndex>::Error,
+            <Self::BlocksIndex as BlocksIndex>::Error,
+            <Self::TransactionsIndex as TransactionsIndex>::Error
+        >
+    >
+    where F: FnMut(ValidationProgress) + Send
+    {
+        parallel::validate_parallel(self, start_block_number, options, progress).await
+    }
+
+    /// Validate blockchain structure using stored
+    /// validation checkpoint.
+    ///
+    /// This method will resume validation from the block
+    /// next to the checkpointed one and update the checkpoint
+    /// with the latest valid block.
+    ///
+    /// Only the checkpointed block is verified to be unchanged
+    /// by comparing its stored and re-calculated hashes with
+    /// the checkpoint, so this method doesn't read the earlier
+    /// blocks. If it was changed then the checkpoint is cleared
+    /// and `CheckpointMismatch` is returned, so the next call
+    /// will validate the whole blockchain again. Changes of the
+    /// earlier blocks are found only by the full validation.
+    async fn validate_incremental<S>(&self, checkpoints: &S) -> Result<
+        BlockchainValidationResult,
+        IncrementalValidationError<
+            <Self::AuthoritiesIndex as AuthoritiesIndex>::Error,
+            <Self::BlocksIndex as BlocksIndex>::Error,
+            <Self::TransactionsIndex as TransactionsIndex>::Error,
+            S::Error
+        >
+    >
+    where S: ValidationCheckpointStore + Send + Sync
+    {
+        let blocks = self.blocks_index();
+
+        let checkpoint = checkpoints.get_checkpoint().await
+            .map_err(IncrementalValidationError::CheckpointStore)?;
+
+        let (block, previous) = match checkpoint {
+            Some(checkpoint) => {
+                let checkpoint_block = blocks.get_block(checkpoint.block_number).await
+                    .map_err(BlockchainValidationError::BlocksIndex)?;
+
+                match checkpoint_block {
+                    // Checkpointed block is unchanged
+                    // so we can continue from the next one.
+                    Some(block) if block.get_hash() == checkpoint.block_hash && block.calculate_hash() == checkpoint.block_hash => {
+                        let next_block = blocks.get_next_block(&block).await
+                            .map_err(BlockchainValidationError::BlocksIndex)?;
+
+                        (next_block, Some(block))
+                    }
+
+                    block => {
+                        checkpoints.clear_checkpoint().await
+                            .map_err(IncrementalValidationError::CheckpointStore)?;
+
+                        return Ok(BlockchainValidationResult::CheckpointMismatch {
+                            block_number: checkpoint.block_number,
+                            expected_hash: checkpoint.block_hash,
+                            got_hash: block.map(|block| block.get_hash())
+                        });
+                    }
+                }
+            }
+
+            None => {
+                let head_block = blocks.get_head_block().await
+                    .map_err(BlockchainValidationError::BlocksIndex)?;
+
+                (head_block, None)
+            }
+        };
+
+        let prev_checkpoint = previous.as_ref()
+            .map(ValidationCheckpoint::from);
+
+        let (result, last_valid_block) = self.validate_blocks(block, previous).await?;
+
+        // Update the checkpoint if new blocks were validated.
+        let new_checkpoint = last_valid_block.as_ref()
+            .map(ValidationCheckpoint::from);
+
+        if let Some(new_checkpoint) = new_checkpoint {
+            if Some(new_checkpoint) != prev_checkpoint {
+                checkpoints.set_checkpoint(new_checkpoint).await
+                    .map_err(IncrementalValidationError::CheckpointStore)?;
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Validate blockchain structure starting
+    /// from the given block.
+    ///
+    /// If the previous block is given then the first
+    /// block will be verified to follow it.
+    ///
+    /// Return validation result and the latest valid block.
+    async fn validate_blocks(&self, mut block: Option<Block>, previous: Option<Block>) -> Result<
+        (BlockchainValidationResult, Option<Block>),
+        BlockchainValidationError<
+            <Self::AuthoritiesIndex as AuthoritiesIndex>::Error,
+            <Self::BlocksIndex as BlocksIndex>::Error,
+            <Self::TransactionsIndex as TransactionsIndex>::Error
+        >
+    > {
+        let authorities = self.authorities_index();
+        let blocks = self.blocks_index();
+        let transactions = self.transactions_index();
+
+        let context = self.validation_context();
+
+        let mut links = ChainLinkValidator::new(&context, block.as_ref(), previous.as_ref());
+
+        // Latest valid block
+        let mut last_valid_block = previous;
+
+        // Validate all the blocks
+        while let Some(curr_block) = block.take() {
+            links.load_history(transactions.as_ref(), &curr_block).await
+                .map_err(BlockchainValidationError::TransactionsIndex)?;
+
+            // Validate block's timestamp, number and previous hash reference
+            if let Some(result) = links.check(&curr_block) {
+                return Ok((result, last_valid_block));
             }
 
             // Validate block's signer
-            let is_authority = authorities.is_authority(&curr_block.validator).await
+            let is_authority = authorities.is_authority(curr_block.authority()).await
                 .map_err(BlockchainValidationError::AuthoritiesIndex)?;
 
             if !is_authority {
-                return Ok(BlockchainValidationResult::InvalidValidator {
+                return Ok((BlockchainValidationResult::InvalidValidator {
                     block_number: curr_block.number,
-                    validator: curr_block.validator
-                });
+                    validator: curr_block.authority().clone()
+                }, last_valid_block));
             }
 
             // Validate block's sign
-            match curr_block.validate() {
-                Ok(reason) if !reason.is_valid() => return Ok(BlockchainValidationResult::InvalidSign {
-                    block_number: curr_block.number,
-                    validator: curr_block.validator,
-                    sign: curr_block.sign,
-                    reason
-                }),
-
-                Err(err) => return Ok(BlockchainValidationResult::SignVerificationError {
-                    block_number: curr_block.number,
-                    validator: curr_block.validator,
-                    sign: curr_block.sign,
-                    reason: err.to_string()
-                }),
-
-                _ => ()
+            if let Some(result) = ChainLinkValidator::check_sign(&curr_block, curr_block.validate_with(&context)) {
+                return Ok((result, last_valid_block));
             }
 
-            prev_created_at = curr_block.created_at;
-            prev_number = curr_block.number;
-
-            prev_block_hash = Some(curr_block.get_hash());
+            links.push(&curr_block);
 
             block = blocks.get_next_block(&curr_block).await
                 .map_err(BlockchainValidationError::BlocksIndex)?;
+
+            last_valid_block = Some(curr_block);
         }
 
-        Ok(BlockchainValidationResult::Valid)
+        Ok((BlockchainValidationResult::Valid, last_valid_block))
     }
 }
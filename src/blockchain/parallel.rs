@@ -0,0 +1,231 @@
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use hyperborealib::exports::tokio;
+
+use tokio::task::JoinHandle;
+
+use super::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParallelValidationOptions {
+    /// Maximal amount of blocks batches
+    /// validated at the same time.
+    ///
+    /// Default is the amount of available CPU cores.
+    pub workers: usize,
+
+    /// Amount of blocks validated by a single worker.
+    ///
+    /// Default is 32.
+    pub batch_size: usize
+}
+
+impl Default for ParallelValidationOptions {
+    fn default() -> Self {
+        Self {
+            workers: std::thread::available_parallelism()
+                .map(usize::from)
+                .unwrap_or(1),
+
+            batch_size: 32
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Information about the blockchain validation progress.
+pub struct ValidationProgress {
+    /// Number of the latest validated block.
+    pub block_number: u64,
+
+    /// Amount of validated blocks.
+    pub validated_blocks: u64,
+
+    /// Time spent on validation.
+    pub elapsed: Duration
+}
+
+impl ValidationProgress {
+    /// Average amount of blocks validated per second.
+    pub fn blocks_per_second(&self) -> f64 {
+        let elapsed = self.elapsed.as_secs_f64();
+
+        if elapsed > 0.0 {
+            self.validated_blocks as f64 / elapsed
+        } else {
+            0.0
+        }
+    }
+}
+
+type BatchValidationResult = Vec<(Block, Result<BlockValidationResult, BlockValidationError>)>;
+
+/// Batches being validated by the workers.
+///
+/// Remaining batches are cancelled when dropped,
+/// e.g. when validation returns early.
+struct PendingBatches {
+    batches: VecDeque<JoinHandle<BatchValidationResult>>,
+    cancelled: Arc<AtomicBool>
+}
+
+impl Drop for PendingBatches {
+    fn drop(&mut self) {
+        // Already running batches can't be aborted
+        // so they're asked to stop instead.
+        self.cancelled.store(true, Ordering::Relaxed);
+
+        for batch in &self.batches {
+            batch.abort();
+        }
+    }
+}
+
+/// Validate blocks and transactions signatures
+/// until the validation is cancelled.
+fn validate_batch(context: &ValidationContext, blocks: Vec<Block>, cancelled: &AtomicBool) -> BatchValidationResult {
+    blocks.into_iter()
+        .take_while(|_| !cancelled.load(Ordering::Relaxed))
+        .map(|block| {
+            let result = block.validate_with(context);
+
+            (block, result)
+        })
+        .collect()
+}
+
+/// Implementation of the `Blockchain::validate_parallel` method.
+pub(crate) async fn validate_parallel<T, F>(
+    blockchain: &T,
+    start_block_number: u64,
+    options: ParallelValidationOptions,
+    mut progress: F
+) -> Result<
+    BlockchainValidationResult,
+    BlockchainValidationError<
+        <T::AuthoritiesIndex as AuthoritiesIndex>::Error,
+        <T::BlocksIndex as BlocksIndex>::Error,
+        <T::TransactionsIndex as TransactionsIndex>::Error
+    >
+>
+where
+    T: Blockchain + Sync + ?Sized,
+    F: FnMut(ValidationProgress) + Send
+{
+    let authorities = blockchain.authorities_index();
+    let blocks = blockchain.blocks_index();
+    let transactions = blockchain.transactions_index();
+
+    let context = blockchain.validation_context();
+
+    let workers = options.workers.max(1);
+    let batch_size = options.batch_size.max(1);
+
+    // Get initial block
+    let mut block = if start_block_number > 0 {
+        blocks.get_block(start_block_number).await
+            .map_err(BlockchainValidationError::BlocksIndex)?
+    } else {
+        blocks.get_head_block().await
+            .map_err(BlockchainValidationError::BlocksIndex)?
+    };
+
+    let mut links = ChainLinkValidator::new(&context, block.as_ref(), None);
+
+    let mut pending = PendingBatches {
+        batches: VecDeque::with_capacity(workers),
+        cancelled: Arc::new(AtomicBool::new(false))
+    };
+
+    // First broken sequential rule.
+    let mut links_result = None;
+
+    let started_at = Instant::now();
+    let mut validated_blocks = 0;
+
+    loop {
+        let mut batch = Vec::with_capacity(batch_size);
+
+        // Fetch new batch of blocks, verifying sequential rules.
+        while batch.len() < batch_size {
+            let Some(curr_block) = block.take() else {
+                break;
+            };
+
+            links.load_history(transactions.as_ref(), &curr_block).await
+                .map_err(BlockchainValidationError::TransactionsIndex)?;
+
+            // Validate block's timestamp, number and previous hash reference
+            if let Some(result) = links.check(&curr_block) {
+                links_result = Some(result);
+
+                break;
+            }
+
+            // Validate block's signer
+            let is_authority = authorities.is_authority(curr_block.authority()).await
+                .map_err(BlockchainValidationError::AuthoritiesIndex)?;
+
+            if !is_authority {
+                links_result = Some(BlockchainValidationResult::InvalidValidator {
+                    block_number: curr_block.number,
+                    validator: curr_block.authority().clone()
+                });
+
+                break;
+            }
+
+            links.push(&curr_block);
+
+            block = blocks.get_next_block(&curr_block).await
+                .map_err(BlockchainValidationError::BlocksIndex)?;
+
+            batch.push(curr_block);
+        }
+
+        let finished = block.is_none();
+
+        // Send the batch to the workers pool.
+        if !batch.is_empty() {
+            let context = context.clone();
+            let cancelled = pending.cancelled.clone();
+
+            pending.batches.push_back(tokio::task::spawn_blocking(move || validate_batch(&context, batch, &cancelled)));
+        }
+
+        // Wait for the oldest batch if all the workers are busy,
+        // or for all of them if there's nothing left to fetch.
+        while pending.batches.len() >= workers || (finished && !pending.batches.is_empty()) {
+            let Some(batch) = pending.batches.pop_front() else {
+                break;
+            };
+
+            let mut block_number = 0;
+
+            for (block, result) in batch.await? {
+                // Validate block's sign
+                if let Some(result) = ChainLinkValidator::check_sign(&block, result) {
+                    return Ok(result);
+                }
+
+                block_number = block.number;
+                validated_blocks += 1;
+            }
+
+            progress(ValidationProgress {
+                block_number,
+                validated_blocks,
+                elapsed: started_at.elapsed()
+            });
+        }
+
+        if finished {
+            break;
+        }
+    }
+
+    Ok(links_result.unwrap_or(BlockchainValidationResult::Valid))
+}
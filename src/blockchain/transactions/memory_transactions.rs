@@ -0,0 +1,530 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{RwLock, PoisonError};
+
+use super::*;
+
+#[derive(Default, Debug)]
+struct MemoryTransactionsState {
+    /// Indexed blocks' transactions.
+    blocks: BTreeMap<u64, Vec<Hash>>,
+
+    /// Transactions' blocks numbers.
+    transactions: HashMap<Hash, u64>,
+
+    /// Transactions included in more than one block.
+    duplicates: Vec<TransactionDuplicate>,
+
+    /// Authors' transactions nonces and their blocks numbers.
+    nonces: HashMap<PublicKey, Vec<(u64, u64)>>,
+
+    /// Recipients' transactions and their blocks numbers.
+    recipients: HashMap<PublicKey, Vec<(Hash, u64)>>,
+
+    /// Referenced transactions' referencing
+    /// transactions and their blocks numbers.
+    references: HashMap<Hash, Vec<(Hash, u64)>>
+}
+
+impl MemoryTransactionsState {
+    /// Store transactions of the given block.
+    fn index_block(&mut self, block: &Block) {
+        let transactions = block.transactions()
+            .iter()
+            .map(Transaction::get_hash)
+            .collect::<Vec<_>>();
+
+        for transaction in &transactions {
+            match self.transactions.get(transaction) {
+                // Do not shadow the original transaction's block.
+                Some(first_block) if *first_block != block.number() => {
+                    self.duplicates.push(TransactionDuplicate {
+                        transaction: *transaction,
+                        first_block: *first_block,
+                        duplicate_block: block.number()
+                    });
+                }
+
+                Some(_) => (),
+
+                None => {
+                    self.transactions.insert(*transaction, block.number());
+                }
+            }
+        }
+
+        for transaction in block.transactions() {
+            if let Some(nonce) = transaction.nonce() {
+                self.nonces.entry(transaction.author().clone())
+                    .or_default()
+                    .push((nonce, block.number()));
+            }
+        }
+
+        for transaction in block.transactions() {
+            let hash = transaction.get_hash();
+
+            // Skip duplicated transactions.
+            if self.transactions.get(&hash) != Some(&block.number()) {
+                continue;
+            }
+
+            for recipient in transaction.body().recipients() {
+                let transactions = self.recipients.entry(recipient.clone())
+                    .or_default();
+
+                if !transactions.contains(&(hash, block.number())) {
+                    transactions.push((hash, block.number()));
+                }
+            }
+
+            for reference in transaction.references() {
+                let transactions = self.references.entry(*reference)
+                    .or_default();
+
+                if !transactions.contains(&(hash, block.number())) {
+                    transactions.push((hash, block.number()));
+                }
+            }
+        }
+
+        self.blocks.insert(block.number(), transactions);
+    }
+}
+
+/// In-memory transactions index implementation.
+///
+/// This struct will index transactions of the
+/// given blocks index in the RAM and lose them
+/// once dropped.
+///
+/// Useful for tests and ephemeral nodes.
+pub struct MemoryTransactionsIndex<T> {
+    state: RwLock<MemoryTransactionsState>,
+    blocks_index: Arc<T>
+}
+
+impl<T> MemoryTransactionsIndex<T>
+where T: BlocksIndex + Send + Sync
+{
+    #[inline]
+    /// Create empty transactions index
+    /// for the given blocks index.
+    pub fn new(blocks_index: Arc<T>) -> Self {
+        Self {
+            state: RwLock::new(MemoryTransactionsState::default()),
+            blocks_index
+        }
+    }
+
+    /// Search for a block with given transaction hash.
+    fn lookup_block(&self, transaction: &Hash) -> Option<u64> {
+        let state = self.state.read()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        state.transactions.get(transaction).copied()
+    }
+
+    async fn index_if_needed(&self) -> Result<(), T::Error> {
+        // Get the latest indexed block number.
+        let last_block_number = {
+            let state = self.state.read()
+                .unwrap_or_else(PoisonError::into_inner);
+
+            state.blocks.last_key_value()
+                .map(|(number, _)| *number)
+        };
+
+        let block = match last_block_number {
+            Some(number) => self.blocks_index.get_block(number).await?,
+            None => self.blocks_index.get_head_block().await?
+        };
+
+        let Some(mut block) = block else {
+            return Ok(());
+        };
+
+        let mut new_blocks = Vec::new();
+
+        // Index the root block if the index is empty.
+        if last_block_number.is_none() {
+            new_blocks.push(block.clone());
+        }
+
+        // Iterate over all the newer blocks.
+        while let Some(next_block) = self.blocks_index.get_next_block(&block).await? {
+            new_blocks.push(next_block.clone());
+
+            block = next_block;
+        }
+
+        if !new_blocks.is_empty() {
+            let mut state = self.state.write()
+                .unwrap_or_else(PoisonError::into_inner);
+
+            for block in &new_blocks {
+                state.index_block(block);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl<T> TransactionsIndex for MemoryTransactionsIndex<T>
+where T: BlocksIndex + Send + Sync
+{
+    type BlocksIndex = T;
+    type Error = T::Error;
+
+    fn blocks_index(&self) -> Arc<Self::BlocksIndex> {
+        self.blocks_index.clone()
+    }
+
+    async fn get_transaction(&self, transaction: &Hash) -> Result<Option<(Transaction, Block)>, Self::Error> {
+        self.index_if_needed().await?;
+
+        let Some(block_number) = self.lookup_block(transaction) else {
+            return Ok(None);
+        };
+
+        let Some(block) = self.blocks_index.get_block(block_number).await? else {
+            return Ok(None);
+        };
+
+        let transaction = block.transactions()
+            .iter()
+            .find(|block_transaction| block_transaction.get_hash() == transaction)
+            .cloned();
+
+        Ok(transaction.map(|transaction| (transaction, block)))
+    }
+
+    async fn get_transaction_block(&self, transaction: &Hash) -> Result<Option<u64>, Self::Error> {
+        self.index_if_needed().await?;
+
+        Ok(self.lookup_block(transaction))
+    }
+
+    async fn has_transaction(&self, transaction: &Hash) -> Result<bool, Self::Error> {
+        self.index_if_needed().await?;
+
+        Ok(self.lookup_block(transaction).is_some())
+    }
+
+    async fn truncate_after(&self, number: u64) -> Result<(), Self::Error> {
+        let mut state = self.state.write()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        let Some(first_removed) = number.checked_add(1) else {
+            return Ok(());
+        };
+
+        let removed_blocks = state.blocks.split_off(&first_removed);
+
+        for (block_number, transactions) in removed_blocks {
+            for transaction in transactions {
+                if state.transactions.get(&transaction) == Some(&block_number) {
+                    state.transactions.remove(&transaction);
+                }
+            }
+        }
+
+        state.duplicates.retain(|duplicate| duplicate.duplicate_block <= number);
+
+        state.nonces.retain(|_, nonces| {
+            nonces.retain(|(_, block_number)| *block_number <= number);
+
+            !nonces.is_empty()
+        });
+
+        state.recipients.retain(|_, transactions| {
+            transactions.retain(|(_, block_number)| *block_number <= number);
+
+            !transactions.is_empty()
+        });
+
+        state.references.retain(|_, transactions| {
+            transactions.retain(|(_, block_number)| *block_number <= number);
+
+            !transactions.is_empty()
+        });
+
+        Ok(())
+    }
+
+    async fn get_duplicates(&self) -> Result<Vec<TransactionDuplicate>, Self::Error> {
+        self.index_if_needed().await?;
+
+        let state = self.state.read()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        Ok(state.duplicates.clone())
+    }
+
+    async fn get_latest_nonce(&self, author: &PublicKey) -> Result<Option<u64>, Self::Error> {
+        self.index_if_needed().await?;
+
+        let state = self.state.read()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        let nonce = state.nonces.get(author)
+            .and_then(|nonces| {
+                nonces.iter()
+                    .map(|(nonce, _)| *nonce)
+                    .max()
+            });
+
+        Ok(nonce)
+    }
+
+    async fn get_latest_nonce_before(&self, author: &PublicKey, block_number: u64) -> Result<Option<u64>, Self::Error> {
+        self.index_if_needed().await?;
+
+        let state = self.state.read()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        let nonce = state.nonces.get(author)
+            .and_then(|nonces| {
+                nonces.iter()
+                    .filter(|(_, nonce_block)| *nonce_block < block_number)
+                    .map(|(nonce, _)| *nonce)
+                    .max()
+            });
+
+        Ok(nonce)
+    }
+
+    async fn get_received_transactions(&self, recipient: &PublicKey) -> Result<Vec<Hash>, Self::Error> {
+        self.index_if_needed().await?;
+
+        let state = self.state.read()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        let transactions = state.recipients.get(recipient)
+            .map(|transactions| {
+                transactions.iter()
+                    .map(|(hash, _)| *hash)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(transactions)
+    }
+
+    async fn get_referencing_transactions(&self, transaction: &Hash) -> Result<Vec<Hash>, Self::Error> {
+        self.index_if_needed().await?;
+
+        let state = self.state.read()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        let transactions = state.references.get(transaction)
+            .map(|transactions| {
+                transactions.iter()
+                    .map(|(hash, _)| *hash)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(transactions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hyperborealib::exports::tokio;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn index() -> Result<(), std::convert::Infallible> {
+        use hyperborealib::crypto::asymmetric::SecretKey;
+
+        use crate::block::prelude::*;
+
+        let validator = SecretKey::random();
+
+        // Prepare transactions
+        let transaction_a = TransactionBuilder::new()
+            .with_body(TransactionBody::Raw(b"Hello, World! x1".to_vec()))
+            .sign(&validator)
+            .unwrap();
+
+        let transaction_b = TransactionBuilder::new()
+            .with_body(TransactionBody::Raw(b"Hello, World! x2".to_vec()))
+            .sign(&validator)
+            .unwrap();
+
+        // Prepare blocks
+        let block_a = BlockBuilder::build_root(&validator);
+
+        let block_b = BlockBuilder::chained(&block_a)
+            .add_transaction(transaction_a.clone())
+            .sign(&validator);
+
+        let block_c = BlockBuilder::chained(&block_b)
+            .add_transaction(transaction_b.clone())
+            .sign(&validator);
+
+        // Prepare indexes
+        let blocks_index = Arc::new(MemoryBlocksIndex::new());
+
+        let transactions_index = MemoryTransactionsIndex::new(blocks_index.clone());
+
+        // Run the tests
+        assert!(!transactions_index.has_transaction(&Hash::MIN).await?);
+        assert!(!transactions_index.has_transaction(&transaction_a.get_hash()).await?);
+
+        // Push A and B
+        blocks_index.insert_block(block_a).await?;
+        blocks_index.insert_block(block_b.clone()).await?;
+
+        assert!(transactions_index.has_transaction(&transaction_a.get_hash()).await?);
+        assert!(!transactions_index.has_transaction(&transaction_b.get_hash()).await?);
+
+        assert_eq!(transactions_index.get_transaction(&transaction_a.get_hash()).await?, Some((
+            transaction_a.clone(),
+            block_b.clone()
+        )));
+
+        // Push C
+        blocks_index.insert_block(block_c.clone()).await?;
+
+        assert_eq!(transactions_index.get_transaction(&transaction_b.get_hash()).await?, Some((
+            transaction_b.clone(),
+            block_c.clone()
+        )));
+
+        // Push D with A again
+        let block_d = BlockBuilder::chained(&block_c)
+            .add_transaction(transaction_a.clone())
+            .sign(&validator);
+
+        blocks_index.insert_block(block_d).await?;
+
+        assert_eq!(transactions_index.get_transaction(&transaction_a.get_hash()).await?, Some((
+            transaction_a.clone(),
+            block_b.clone()
+        )));
+
+        assert_eq!(transactions_index.get_duplicates().await?, vec![
+            TransactionDuplicate {
+                transaction: transaction_a.get_hash(),
+                first_block: 1,
+                duplicate_block: 3
+            }
+        ]);
+
+        // Truncate C and D
+        blocks_index.truncate_after(1).await?;
+        transactions_index.truncate_after(1).await?;
+
+        assert!(transactions_index.has_transaction(&transaction_a.get_hash()).await?);
+        assert!(!transactions_index.has_transaction(&transaction_b.get_hash()).await?);
+
+        assert!(transactions_index.get_duplicates().await?.is_empty());
+
+        // Push transactions with nonces
+        let transaction_c = TransactionBuilder::new()
+            .with_nonce(0)
+            .with_body(TransactionBody::Raw(b"Hello, World! x3".to_vec()))
+            .sign(&validator)
+            .unwrap();
+
+        let transaction_d = TransactionBuilder::new()
+            .with_nonce(1)
+            .with_body(TransactionBody::Raw(b"Hello, World! x4".to_vec()))
+            .sign(&validator)
+            .unwrap();
+
+        let block_c = BlockBuilder::chained(&block_b)
+            .add_transaction(transaction_c)
+            .add_transaction(transaction_d)
+            .sign(&validator);
+
+        assert_eq!(transactions_index.get_latest_nonce(&validator.public_key()).await?, None);
+
+        blocks_index.insert_block(block_c).await?;
+
+        assert_eq!(transactions_index.get_latest_nonce(&validator.public_key()).await?, Some(1));
+        assert_eq!(transactions_index.get_latest_nonce_before(&validator.public_key(), 2).await?, None);
+        assert_eq!(transactions_index.get_latest_nonce_before(&validator.public_key(), 3).await?, Some(1));
+
+        // Truncate C
+        blocks_index.truncate_after(1).await?;
+        transactions_index.truncate_after(1).await?;
+
+        assert_eq!(transactions_index.get_latest_nonce(&validator.public_key()).await?, None);
+
+        // Push messages
+        let alice = SecretKey::random();
+        let bob = SecretKey::random();
+
+        let message = TransactionBuilder::new()
+            .with_body(MessageTransactionBuilder::new()
+                .with_receiver(alice.public_key())
+                .with_content(b"Hello, Alice!")
+                .build(&validator)
+                .unwrap())
+            .sign(&validator)
+            .unwrap();
+
+        let group_message = TransactionBuilder::new()
+            .with_body(GroupMessageTransactionBuilder::new()
+                .with_recipients([alice.public_key(), bob.public_key()])
+                .with_content(b"Hello, World!")
+                .build(&validator)
+                .unwrap())
+            .sign(&validator)
+            .unwrap();
+
+        let block_c = BlockBuilder::chained(&block_b)
+            .add_transaction(message.clone())
+            .add_transaction(group_message.clone())
+            .sign(&validator);
+
+        blocks_index.insert_block(block_c).await?;
+
+        assert_eq!(transactions_index.get_received_transactions(&alice.public_key()).await?, vec![
+            message.get_hash(),
+            group_message.get_hash()
+        ]);
+
+        assert_eq!(transactions_index.get_received_transactions(&bob.public_key()).await?, vec![
+            group_message.get_hash()
+        ]);
+
+        // Truncate C
+        blocks_index.truncate_after(1).await?;
+        transactions_index.truncate_after(1).await?;
+
+        assert!(transactions_index.get_received_transactions(&alice.public_key()).await?.is_empty());
+
+        // Push a reply to A
+        let reply = TransactionBuilder::new()
+            .with_reference(transaction_a.get_hash())
+            .with_body(TransactionBody::Raw(b"Hello, A!".to_vec()))
+            .sign(&validator)
+            .unwrap();
+
+        let block_c = BlockBuilder::chained(&block_b)
+            .add_transaction(reply.clone())
+            .sign(&validator);
+
+        blocks_index.insert_block(block_c).await?;
+
+        assert_eq!(transactions_index.get_referencing_transactions(&transaction_a.get_hash()).await?, vec![
+            reply.get_hash()
+        ]);
+
+        assert!(transactions_index.get_referencing_transactions(&reply.get_hash()).await?.is_empty());
+
+        // Truncate C
+        blocks_index.truncate_after(1).await?;
+        transactions_index.truncate_after(1).await?;
+
+        assert!(transactions_index.get_referencing_transactions(&transaction_a.get_hash()).await?.is_empty());
+
+        Ok(())
+    }
+}
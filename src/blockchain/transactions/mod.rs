@@ -1,10 +1,29 @@
 use std::sync::Arc;
 
+use hyperborealib::crypto::asymmetric::PublicKey;
+
 use crate::prelude::*;
 
 mod transactions_file;
+mod memory_transactions;
 
 pub use transactions_file::*;
+pub use memory_transactions::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// Transaction included in more than one block.
+pub struct TransactionDuplicate {
+    /// Hash of the transaction.
+    pub transaction: Hash,
+
+    /// Number of the block where the transaction
+    /// was included for the first time.
+    pub first_block: u64,
+
+    /// Number of the block which includes
+    /// the transaction again.
+    pub duplicate_block: u64
+}
 
 #[async_trait::async_trait]
 /// This trait implementation should manage information
@@ -18,10 +37,63 @@ pub trait TransactionsIndex {
     fn blocks_index(&self) -> Arc<Self::BlocksIndex>;
 
     /// Try searching for transaction in the index.
+    ///
+    /// If the transaction is included in more than
+    /// one block then the first one is returned.
     async fn get_transaction(&self, transaction: &Hash) -> Result<Option<(Transaction, Block)>, Self::Error>;
 
+    /// Get number of the block where the transaction
+    /// with given hash was included for the first time.
+    async fn get_transaction_block(&self, transaction: &Hash) -> Result<Option<u64>, Self::Error> {
+        Ok(self.get_transaction(transaction).await?.map(|(_, block)| block.number()))
+    }
+
     /// Check if transaction with given hash is indexed.
     async fn has_transaction(&self, transaction: &Hash) -> Result<bool, Self::Error> {
-        Ok(self.get_transaction(transaction).await?.is_some())
+        Ok(self.get_transaction_block(transaction).await?.is_some())
     }
+
+    /// Remove transactions of all the blocks with
+    /// numbers higher than the given one from the index.
+    ///
+    /// This method should be called after the blocks
+    /// index was truncated to prevent removed blocks'
+    /// transactions from being returned.
+    async fn truncate_after(&self, number: u64) -> Result<(), Self::Error>;
+
+    /// Get list of transactions included in more
+    /// than one block, found while indexing them.
+    ///
+    /// Duplicated inclusions are not indexed
+    /// so they can't shadow the original ones.
+    async fn get_duplicates(&self) -> Result<Vec<TransactionDuplicate>, Self::Error>;
+
+    /// Get the highest nonce of the indexed
+    /// transactions made by the given author.
+    ///
+    /// Return `None` if the author has no
+    /// transactions with nonces.
+    async fn get_latest_nonce(&self, author: &PublicKey) -> Result<Option<u64>, Self::Error>;
+
+    /// Get the highest nonce of the transactions made
+    /// by the given author and included in the blocks
+    /// with numbers lower than the given one.
+    ///
+    /// Return `None` if the author has no such
+    /// transactions with nonces.
+    async fn get_latest_nonce_before(&self, author: &PublicKey, block_number: u64) -> Result<Option<u64>, Self::Error>;
+
+    /// Get hashes of the indexed transactions
+    /// sent to the given recipient.
+    ///
+    /// Both messages and group messages which
+    /// list the recipient are returned, ordered
+    /// by their blocks numbers.
+    async fn get_received_transactions(&self, recipient: &PublicKey) -> Result<Vec<Hash>, Self::Error>;
+
+    /// Get hashes of the indexed transactions
+    /// referencing the given transaction.
+    ///
+    /// Transactions are ordered by their blocks numbers.
+    async fn get_referencing_transactions(&self, transaction: &Hash) -> Result<Vec<Hash>, Self::Error>;
 }
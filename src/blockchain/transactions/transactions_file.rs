@@ -1,10 +1,12 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::io::SeekFrom;
+use std::collections::HashMap;
 
 use hyperborealib::exports::tokio;
 
 use tokio::fs::File;
+use tokio::sync::{Mutex, MutexGuard, MappedMutexGuard};
 
 use tokio::io::{
     AsyncReadExt,
@@ -30,6 +32,14 @@ pub enum TransactionsFileError<T> {
 /// This struct will store transactions info
 /// in a separate file for fast lookups.
 ///
+/// Indexed transactions, nonces, recipients and references
+/// are kept in the RAM once the index files are read, so lookups
+/// and indexing of new blocks don't need to scan the whole files.
+/// Every indexed transaction must be known to detect duplicated
+/// inclusions, so the in-memory copy is not bounded and takes
+/// about 40 bytes (plus hash map's overhead) per transaction,
+/// nonce, recipient and reference.
+///
 /// For large scale applications this solution
 /// may not be good enough.
 ///
@@ -45,9 +55,79 @@ pub enum TransactionsFileError<T> {
 /// [u64 prev_block_entry_pos][u64 block_number]
 /// [u16 transactions_number]<transactions_hashes>
 /// ```
+///
+/// Transactions which are already indexed are not stored
+/// again. Instead they're appended to a separate duplicates
+/// file with the same name and `.duplicates` extension.
+///
+/// ## Duplicates structure
+///
+/// ```text
+/// <[32 bytes transaction_hash][u64 first_block][u64 duplicate_block]>
+/// ```
+///
+/// Nonces of the indexed transactions are stored in
+/// a file with the same name and `.nonces` extension.
+/// Authors are identified by hashes of their public keys.
+///
+/// ## Nonces structure
+///
+/// ```text
+/// <[32 bytes author_hash][u64 nonce][u64 block_number]>
+/// ```
+///
+/// Recipients of the indexed messages and group messages
+/// are stored in a file with the same name and `.recipients`
+/// extension, identified the same way as authors.
+///
+/// ## Recipients structure
+///
+/// ```text
+/// <[32 bytes recipient_hash][32 bytes transaction_hash][u64 block_number]>
+/// ```
+///
+/// References between the indexed transactions are stored
+/// in a file with the same name and `.references` extension.
+///
+/// ## References structure
+///
+/// ```text
+/// <[32 bytes referenced_hash][32 bytes transaction_hash][u64 block_number]>
+/// ```
 pub struct TransactionsFile<T> {
     file: PathBuf,
-    blocks_index: Arc<T>
+    duplicates_file: PathBuf,
+    nonces_file: PathBuf,
+    recipients_file: PathBuf,
+    references_file: PathBuf,
+    blocks_index: Arc<T>,
+
+    /// In-memory copy of the index, read from the
+    /// files on first use and dropped on truncation.
+    ///
+    /// It's not bounded because a partial copy couldn't
+    /// tell that a transaction was never indexed without
+    /// scanning the whole index file for every block.
+    cache: Mutex<Option<TransactionsFileCache>>
+}
+
+#[derive(Debug, Default)]
+struct TransactionsFileCache {
+    /// Indexed transactions and numbers of the blocks
+    /// they were included to for the first time.
+    transactions: HashMap<Hash, u64>,
+
+    /// Authors' transactions nonces and their blocks
+    /// numbers, grouped by hashes of the authors.
+    nonces: HashMap<Hash, Vec<(u64, u64)>>,
+
+    /// Transactions received by the messages'
+    /// recipients, grouped by hashes of the recipients.
+    recipients: HashMap<Hash, Vec<Hash>>,
+
+    /// Transactions referencing the indexed
+    /// ones, grouped by the referenced hashes.
+    references: HashMap<Hash, Vec<Hash>>
 }
 
 impl<T> TransactionsFile<T>
@@ -57,18 +137,97 @@ where T: BlocksIndex + Send + Sync
     pub async fn open(path: impl Into<PathBuf>, blocks_index: Arc<T>) -> std::io::Result<Self> {
         let file: PathBuf = path.into();
 
+        let duplicates_file = file.with_extension("duplicates");
+        let nonces_file = file.with_extension("nonces");
+        let recipients_file = file.with_extension("recipients");
+        let references_file = file.with_extension("references");
+
         if !file.exists() {
             tokio::fs::write(&file, &0u64.to_be_bytes()).await?;
         }
 
+        if !duplicates_file.exists() {
+            tokio::fs::write(&duplicates_file, []).await?;
+        }
+
+        if !nonces_file.exists() {
+            tokio::fs::write(&nonces_file, []).await?;
+        }
+
+        if !recipients_file.exists() {
+            tokio::fs::write(&recipients_file, []).await?;
+        }
+
+        if !references_file.exists() {
+            tokio::fs::write(&references_file, []).await?;
+        }
+
         Ok(Self {
             file,
-            blocks_index
+            duplicates_file,
+            nonces_file,
+            recipients_file,
+            references_file,
+            blocks_index,
+            cache: Mutex::new(None)
         })
     }
 
+    /// Lock in-memory copy of the index,
+    /// reading the index files if needed.
+    ///
+    /// Entries of the other index files which were written
+    /// for a block not stored in the main index file (e.g.
+    /// because of a crash) are removed before reading.
+    async fn lock_cache(&self) -> std::io::Result<MutexGuard<'_, Option<TransactionsFileCache>>> {
+        let mut cache = self.cache.lock().await;
+
+        if cache.is_none() {
+            self.truncate_entries_after(self.read_last_block_number().await?).await?;
+
+            let mut nonces = HashMap::<_, Vec<_>>::new();
+
+            for (author, nonce, block_number) in self.read_nonces().await? {
+                nonces.entry(author)
+                    .or_default()
+                    .push((nonce, block_number));
+            }
+
+            let mut recipients = HashMap::<_, Vec<_>>::new();
+
+            for (recipient, transaction, _) in self.read_recipients().await? {
+                let transactions = recipients.entry(recipient)
+                    .or_default();
+
+                if !transactions.contains(&transaction) {
+                    transactions.push(transaction);
+                }
+            }
+
+            let mut references = HashMap::<_, Vec<_>>::new();
+
+            for (reference, transaction, _) in self.read_references().await? {
+                let transactions = references.entry(reference)
+                    .or_default();
+
+                if !transactions.contains(&transaction) {
+                    transactions.push(transaction);
+                }
+            }
+
+            *cache = Some(TransactionsFileCache {
+                transactions: self.read_transactions().await?,
+                nonces,
+                recipients,
+                references
+            });
+        }
+
+        Ok(cache)
+    }
+
     /// Append block to the index file.
-    async fn index_block(&self, block: Block) -> std::io::Result<()> {
+    async fn index_block(&self, cache: &mut TransactionsFileCache, block: Block) -> std::io::Result<()> {
         let file = File::options()
             .read(true)
             .write(true)
@@ -83,11 +242,125 @@ where T: BlocksIndex + Send + Sync
         // Seek the end of the index file.
         let new_block_pos = file.seek(SeekFrom::End(0)).await?;
 
-        // Get list of block transactions' hashes.
-        let transactions = block.transactions()
-            .iter()
-            .map(|transaction| transaction.get_hash().as_bytes())
-            .collect::<Vec<_>>();
+        // Get list of block transactions' hashes
+        // except the already indexed ones.
+        let mut transactions = Vec::with_capacity(block.transactions().len());
+        let mut duplicates = Vec::new();
+        let mut nonces = Vec::new();
+        let mut recipients = Vec::new();
+        let mut references = Vec::new();
+
+        for transaction in block.transactions() {
+            let hash = transaction.get_hash();
+
+            match cache.transactions.get(&hash) {
+                Some(first_block) => duplicates.push(TransactionDuplicate {
+                    transaction: hash,
+                    first_block: *first_block,
+                    duplicate_block: block.number()
+                }),
+
+                None => {
+                    transactions.push(hash);
+
+                    if let Some(nonce) = transaction.nonce() {
+                        nonces.push((Self::key_hash(transaction.author()), nonce));
+                    }
+
+                    for recipient in transaction.body().recipients() {
+                        let recipient = (Self::key_hash(recipient), hash);
+
+                        if !recipients.contains(&recipient) {
+                            recipients.push(recipient);
+                        }
+                    }
+
+                    for reference in transaction.references() {
+                        references.push((*reference, hash));
+                    }
+                }
+            }
+        }
+
+        // Entries of the other index files are written first.
+        // They're ignored and removed on the next index read
+        // until the block is stored in the main index file.
+
+        // Store duplicated transactions.
+        if !duplicates.is_empty() {
+            let mut duplicates_buffer = Vec::with_capacity(duplicates.len() * (Hash::BYTES + 16));
+
+            for duplicate in duplicates {
+                duplicates_buffer.extend_from_slice(&duplicate.transaction.as_bytes());
+                duplicates_buffer.extend_from_slice(&duplicate.first_block.to_be_bytes());
+                duplicates_buffer.extend_from_slice(&duplicate.duplicate_block.to_be_bytes());
+            }
+
+            let mut file = File::options()
+                .append(true)
+                .open(&self.duplicates_file)
+                .await?;
+
+            file.write_all(&duplicates_buffer).await?;
+            file.flush().await?;
+        }
+
+        // Store transactions' nonces.
+        if !nonces.is_empty() {
+            let mut nonces_buffer = Vec::with_capacity(nonces.len() * (Hash::BYTES + 16));
+
+            for (author, nonce) in &nonces {
+                nonces_buffer.extend_from_slice(&author.as_bytes());
+                nonces_buffer.extend_from_slice(&nonce.to_be_bytes());
+                nonces_buffer.extend_from_slice(&block.number().to_be_bytes());
+            }
+
+            let mut file = File::options()
+                .append(true)
+                .open(&self.nonces_file)
+                .await?;
+
+            file.write_all(&nonces_buffer).await?;
+            file.flush().await?;
+        }
+
+        // Store messages' recipients.
+        if !recipients.is_empty() {
+            let mut recipients_buffer = Vec::with_capacity(recipients.len() * (Hash::BYTES * 2 + 8));
+
+            for (recipient, transaction) in &recipients {
+                recipients_buffer.extend_from_slice(&recipient.as_bytes());
+                recipients_buffer.extend_from_slice(&transaction.as_bytes());
+                recipients_buffer.extend_from_slice(&block.number().to_be_bytes());
+            }
+
+            let mut file = File::options()
+                .append(true)
+                .open(&self.recipients_file)
+                .await?;
+
+            file.write_all(&recipients_buffer).await?;
+            file.flush().await?;
+        }
+
+        // Store transactions' references.
+        if !references.is_empty() {
+            let mut references_buffer = Vec::with_capacity(references.len() * (Hash::BYTES * 2 + 8));
+
+            for (reference, transaction) in &references {
+                references_buffer.extend_from_slice(&reference.as_bytes());
+                references_buffer.extend_from_slice(&transaction.as_bytes());
+                references_buffer.extend_from_slice(&block.number().to_be_bytes());
+            }
+
+            let mut file = File::options()
+                .append(true)
+                .open(&self.references_file)
+                .await?;
+
+            file.write_all(&references_buffer).await?;
+            file.flush().await?;
+        }
 
         // Block buffer.
         //
@@ -108,8 +381,8 @@ where T: BlocksIndex + Send + Sync
         block_buffer.extend_from_slice(&(transactions.len() as u16).to_be_bytes());
 
         // Write all the transactions.
-        for transaction in transactions {
-            block_buffer.extend_from_slice(&transaction);
+        for transaction in &transactions {
+            block_buffer.extend_from_slice(&transaction.as_bytes());
         }
 
         // Write block's buffer to the file.
@@ -121,13 +394,254 @@ where T: BlocksIndex + Send + Sync
 
         file.flush().await?;
 
-        dbg!(new_block_pos);
+        // Remember indexed block.
+        for transaction in transactions {
+            cache.transactions.insert(transaction, block.number());
+        }
+
+        for (author, nonce) in nonces {
+            cache.nonces.entry(author)
+                .or_default()
+                .push((nonce, block.number()));
+        }
+
+        for (recipient, transaction) in recipients {
+            let transactions = cache.recipients.entry(recipient)
+                .or_default();
+
+            if !transactions.contains(&transaction) {
+                transactions.push(transaction);
+            }
+        }
+
+        for (reference, transaction) in references {
+            let transactions = cache.references.entry(reference)
+                .or_default();
+
+            if !transactions.contains(&transaction) {
+                transactions.push(transaction);
+            }
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    /// Identifier of the public key in
+    /// the nonces and recipients files.
+    fn key_hash(key: &PublicKey) -> Hash {
+        Hash::hash_slice(key.to_bytes())
+    }
+
+    #[inline]
+    /// Read all the indexed recipients.
+    async fn read_recipients(&self) -> std::io::Result<Vec<(Hash, Hash, u64)>> {
+        Self::read_hash_pairs(&self.recipients_file).await
+    }
+
+    #[inline]
+    /// Read all the indexed references.
+    async fn read_references(&self) -> std::io::Result<Vec<(Hash, Hash, u64)>> {
+        Self::read_hash_pairs(&self.references_file).await
+    }
+
+    /// Read all the `[32 bytes key][32 bytes transaction_hash][u64 block_number]`
+    /// entries from the given file.
+    async fn read_hash_pairs(path: &Path) -> std::io::Result<Vec<(Hash, Hash, u64)>> {
+        let entries = tokio::fs::read(path).await?;
+
+        let entries = entries.chunks_exact(Hash::BYTES * 2 + 8)
+            .map(|entry| {
+                let mut key = [0; Hash::BYTES];
+                let mut transaction = [0; Hash::BYTES];
+                let mut block_number = [0; 8];
+
+                key.copy_from_slice(&entry[..Hash::BYTES]);
+                transaction.copy_from_slice(&entry[Hash::BYTES..Hash::BYTES * 2]);
+                block_number.copy_from_slice(&entry[Hash::BYTES * 2..]);
+
+                (
+                    Hash::from_bytes(key),
+                    Hash::from_bytes(transaction),
+                    u64::from_be_bytes(block_number)
+                )
+            })
+            .collect();
+
+        Ok(entries)
+    }
+
+    /// Read all the indexed nonces.
+    async fn read_nonces(&self) -> std::io::Result<Vec<(Hash, u64, u64)>> {
+        let nonces = tokio::fs::read(&self.nonces_file).await?;
+
+        let nonces = nonces.chunks_exact(Hash::BYTES + 16)
+            .map(|entry| {
+                let mut author = [0; Hash::BYTES];
+                let mut nonce = [0; 8];
+                let mut block_number = [0; 8];
+
+                author.copy_from_slice(&entry[..Hash::BYTES]);
+                nonce.copy_from_slice(&entry[Hash::BYTES..Hash::BYTES + 8]);
+                block_number.copy_from_slice(&entry[Hash::BYTES + 8..]);
+
+                (
+                    Hash::from_bytes(author),
+                    u64::from_be_bytes(nonce),
+                    u64::from_be_bytes(block_number)
+                )
+            })
+            .collect();
+
+        Ok(nonces)
+    }
+
+    /// Read all the duplicated transactions.
+    async fn read_duplicates(&self) -> std::io::Result<Vec<TransactionDuplicate>> {
+        let duplicates = tokio::fs::read(&self.duplicates_file).await?;
+
+        let duplicates = duplicates.chunks_exact(Hash::BYTES + 16)
+            .map(|entry| {
+                let mut transaction = [0; Hash::BYTES];
+                let mut first_block = [0; 8];
+                let mut duplicate_block = [0; 8];
+
+                transaction.copy_from_slice(&entry[..Hash::BYTES]);
+                first_block.copy_from_slice(&entry[Hash::BYTES..Hash::BYTES + 8]);
+                duplicate_block.copy_from_slice(&entry[Hash::BYTES + 8..]);
+
+                TransactionDuplicate {
+                    transaction: Hash::from_bytes(transaction),
+                    first_block: u64::from_be_bytes(first_block),
+                    duplicate_block: u64::from_be_bytes(duplicate_block)
+                }
+            })
+            .collect();
+
+        Ok(duplicates)
+    }
+
+    /// Remove all the blocks with numbers higher
+    /// than the given one from the index file.
+    ///
+    /// Since newer blocks are always appended to the
+    /// end of the file we can pop them one by one
+    /// and then truncate the file.
+    async fn rollback_after(&self, number: u64) -> std::io::Result<()> {
+        // Drop in-memory copy of the index so it will be
+        // read again from the truncated files. The lock is
+        // held to prevent indexing during the truncation.
+        let mut cache = self.cache.lock().await;
+
+        *cache = None;
+
+        let mut file = File::options()
+            .read(true)
+            .write(true)
+            .open(&self.file)
+            .await?;
+
+        // Get reference to the last block.
+        let mut last_block_pos = file.read_u64().await?;
+
+        // Position from which the file should be truncated.
+        let mut truncate_pos = None;
+
+        while last_block_pos > 0 {
+            // Seek the entry position of the block.
+            file.seek(SeekFrom::Start(last_block_pos)).await?;
+
+            // Read info about the block.
+            let prev_block_pos = file.read_u64().await?;
+            let block_number = file.read_u64().await?;
+
+            // Stop popping blocks when we've reached the kept one.
+            if block_number <= number {
+                break;
+            }
+
+            truncate_pos = Some(last_block_pos);
+            last_block_pos = prev_block_pos;
+        }
+
+        if let Some(truncate_pos) = truncate_pos {
+            // Update reference to the last block.
+            file.seek(SeekFrom::Start(0)).await?;
+            file.write_u64(last_block_pos).await?;
+            file.flush().await?;
+
+            // Remove popped blocks from the file.
+            file.set_len(truncate_pos).await?;
+        }
+
+        // Remove entries of the popped blocks
+        // from the other index files.
+        self.truncate_entries_after(Some(number)).await
+    }
+
+    /// Remove entries of the blocks with numbers higher
+    /// than the given one from the other index files.
+    ///
+    /// All the entries are removed if `None` is given.
+    async fn truncate_entries_after(&self, number: Option<u64>) -> std::io::Result<()> {
+        Self::truncate_entries(&self.duplicates_file, Hash::BYTES + 16, number).await?;
+        Self::truncate_entries(&self.nonces_file, Hash::BYTES + 16, number).await?;
+        Self::truncate_entries(&self.recipients_file, Hash::BYTES * 2 + 8, number).await?;
+        Self::truncate_entries(&self.references_file, Hash::BYTES * 2 + 8, number).await?;
+
+        Ok(())
+    }
+
+    /// Remove entries with block numbers higher than the
+    /// given one from the file of fixed size entries.
+    ///
+    /// Block number is stored as the last `u64`
+    /// of each entry.
+    async fn truncate_entries(path: &Path, entry_size: usize, number: Option<u64>) -> std::io::Result<()> {
+        let entries = tokio::fs::read(path).await?;
+
+        let kept_entries = entries.chunks_exact(entry_size)
+            .filter(|entry| {
+                let mut block_number = [0; 8];
+
+                block_number.copy_from_slice(&entry[entry_size - 8..]);
+
+                number.is_some_and(|number| u64::from_be_bytes(block_number) <= number)
+            })
+            .flatten()
+            .copied()
+            .collect::<Vec<u8>>();
+
+        if kept_entries.len() < entries.len() {
+            tokio::fs::write(path, kept_entries).await?;
+        }
 
         Ok(())
     }
 
-    /// Search for a block with given transaction hash.
-    async fn lookup_block(&self, transaction: &Hash) -> std::io::Result<Option<u64>> {
+    /// Read number of the last block stored
+    /// in the main index file.
+    async fn read_last_block_number(&self) -> std::io::Result<Option<u64>> {
+        let mut file = File::open(&self.file).await?;
+
+        // Get reference to the last block.
+        let last_block_pos = file.read_u64().await?;
+
+        if last_block_pos == 0 {
+            return Ok(None);
+        }
+
+        // Seek to this block, skipping the prev block reference.
+        file.seek(SeekFrom::Start(last_block_pos + 8)).await?;
+
+        Ok(Some(file.read_u64().await?))
+    }
+
+    /// Read all the indexed transactions and numbers
+    /// of the blocks they were included to first.
+    async fn read_transactions(&self) -> std::io::Result<HashMap<Hash, u64>> {
+        let mut transactions = HashMap::new();
+
         let mut file = BufReader::new(File::open(&self.file).await?);
 
         // Get reference to the last block.
@@ -147,57 +661,58 @@ where T: BlocksIndex + Send + Sync
             for _ in 0..transactions_num {
                 let mut block_transaction = [0; Hash::BYTES];
 
-                // wtf is this warning??
-                #[allow(clippy::needless_range_loop)]
-                for j in 0..Hash::BYTES {
-                    block_transaction[j] = file.read_u8().await?;
-                }
+                file.read_exact(&mut block_transaction).await?;
 
-                // If the block's transaction is what we search for
-                // then return its block number.
-                if block_transaction == transaction {
-                    return Ok(Some(block_number));
-                }
+                // Blocks are read from the newest to the oldest
+                // so the earliest one will overwrite others.
+                transactions.insert(Hash::from_bytes(block_transaction), block_number);
             }
         }
 
-        Ok(None)
+        Ok(transactions)
     }
 
-    async fn index_if_needed(&self) -> Result<(), TransactionsFileError<T::Error>> {
-        let mut file = BufReader::new(File::open(&self.file).await?);
+    /// Index new blocks of the blocks index and
+    /// lock the updated in-memory copy of the index.
+    ///
+    /// Holding the lock prevents concurrent indexing
+    /// and truncation of the index files.
+    async fn index_if_needed(&self) -> Result<MappedMutexGuard<'_, TransactionsFileCache>, TransactionsFileError<T::Error>> {
+        let mut guard = self.lock_cache().await?;
 
-        // Get reference to the last block.
-        let last_entry_pos = file.read_u64().await?;
+        // Drop in-memory copy of the index if indexing failed
+        // so it will be read again and partially written
+        // entries will be removed from the index files.
+        let result = self.index_blocks(guard.get_or_insert_with(TransactionsFileCache::default)).await;
 
-        let index = self.blocks_index();
+        if let Err(err) = result {
+            *guard = None;
 
-        // Get the latest indexed block.
-        let mut empty_index = false;
+            return Err(err);
+        }
 
-        let block = if last_entry_pos > 0 {
-            // Seek to this block, skipping the prev block reference.
-            file.seek(SeekFrom::Start(last_entry_pos + 8)).await?;
+        Ok(MutexGuard::map(guard, |cache| cache.get_or_insert_with(TransactionsFileCache::default)))
+    }
 
-            // Read latest indexed block number.
-            let block_number = file.read_u64().await?;
+    /// Index new blocks of the blocks index.
+    async fn index_blocks(&self, cache: &mut TransactionsFileCache) -> Result<(), TransactionsFileError<T::Error>> {
+        let index = self.blocks_index();
 
-            index.get_block(block_number).await
-                .map_err(TransactionsFileError::BlocksIndex)?
-        } else {
-            empty_index = true;
+        // Get the latest indexed block.
+        let last_block_number = self.read_last_block_number().await?;
 
-            index.get_head_block().await
-                .map_err(TransactionsFileError::BlocksIndex)?
+        let block = match last_block_number {
+            Some(block_number) => index.get_block(block_number).await,
+            None => index.get_head_block().await
         };
 
-        let Some(mut block) = block else {
+        let Some(mut block) = block.map_err(TransactionsFileError::BlocksIndex)? else {
             return Ok(());
         };
 
         // Index the root block if the index is empty.
-        if empty_index {
-            self.index_block(block.clone()).await?;
+        if last_block_number.is_none() {
+            self.index_block(cache, block.clone()).await?;
         }
 
         // Iterate over all the newer blocks.
@@ -210,7 +725,7 @@ where T: BlocksIndex + Send + Sync
             };
 
             // Index the newer block.
-            self.index_block(next_block.clone()).await?;
+            self.index_block(cache, next_block.clone()).await?;
 
             block = next_block;
         }
@@ -231,9 +746,9 @@ where T: BlocksIndex + Send + Sync
     }
 
     async fn get_transaction(&self, transaction: &Hash) -> Result<Option<(Transaction, Block)>, Self::Error> {
-        self.index_if_needed().await?;
+        let block_number = self.get_transaction_block(transaction).await?;
 
-        match self.lookup_block(transaction).await? {
+        match block_number {
             Some(block_number) => {
                 let index = self.blocks_index();
 
@@ -260,10 +775,75 @@ where T: BlocksIndex + Send + Sync
         }
     }
 
+    async fn get_transaction_block(&self, transaction: &Hash) -> Result<Option<u64>, Self::Error> {
+        let cache = self.index_if_needed().await?;
+
+        Ok(cache.transactions.get(transaction).copied())
+    }
+
     async fn has_transaction(&self, transaction: &Hash) -> Result<bool, Self::Error> {
-        self.index_if_needed().await?;
+        let cache = self.index_if_needed().await?;
+
+        Ok(cache.transactions.contains_key(transaction))
+    }
 
-        Ok(self.lookup_block(transaction).await?.is_some())
+    async fn truncate_after(&self, number: u64) -> Result<(), Self::Error> {
+        self.rollback_after(number).await?;
+
+        Ok(())
+    }
+
+    async fn get_duplicates(&self) -> Result<Vec<TransactionDuplicate>, Self::Error> {
+        let _cache = self.index_if_needed().await?;
+
+        Ok(self.read_duplicates().await?)
+    }
+
+    async fn get_latest_nonce(&self, author: &PublicKey) -> Result<Option<u64>, Self::Error> {
+        let cache = self.index_if_needed().await?;
+
+        let nonce = cache.nonces.get(&Self::key_hash(author))
+            .and_then(|nonces| {
+                nonces.iter()
+                    .map(|(nonce, _)| *nonce)
+                    .max()
+            });
+
+        Ok(nonce)
+    }
+
+    async fn get_latest_nonce_before(&self, author: &PublicKey, block_number: u64) -> Result<Option<u64>, Self::Error> {
+        let cache = self.index_if_needed().await?;
+
+        let nonce = cache.nonces.get(&Self::key_hash(author))
+            .and_then(|nonces| {
+                nonces.iter()
+                    .filter(|(_, nonce_block)| *nonce_block < block_number)
+                    .map(|(nonce, _)| *nonce)
+                    .max()
+            });
+
+        Ok(nonce)
+    }
+
+    async fn get_received_transactions(&self, recipient: &PublicKey) -> Result<Vec<Hash>, Self::Error> {
+        let cache = self.index_if_needed().await?;
+
+        let transactions = cache.recipients.get(&Self::key_hash(recipient))
+            .cloned()
+            .unwrap_or_default();
+
+        Ok(transactions)
+    }
+
+    async fn get_referencing_transactions(&self, transaction: &Hash) -> Result<Vec<Hash>, Self::Error> {
+        let cache = self.index_if_needed().await?;
+
+        let transactions = cache.references.get(transaction)
+            .cloned()
+            .unwrap_or_default();
+
+        Ok(transactions)
     }
 }
 
@@ -392,6 +972,225 @@ mod tests {
             block_d.clone()
         )));
 
+        // Reopen the index
+        let transactions_index = TransactionsFile::open(
+            path.join("transactions"),
+            blocks_index.clone()
+        ).await?;
+
+        assert_eq!(transactions_index.get_transaction_block(&transaction_a.get_hash()).await?, Some(1));
+        assert_eq!(transactions_index.get_transaction_block(&transaction_b.get_hash()).await?, Some(3));
+        assert_eq!(transactions_index.get_transaction_block(&Hash::MIN).await?, None);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn truncate() -> Result<(), TransactionsFileError<std::convert::Infallible>> {
+        use hyperborealib::crypto::asymmetric::SecretKey;
+
+        use crate::block::prelude::*;
+
+        let path = crate::utils::test_dir("transactions-file-truncate-test")?;
+
+        let validator = SecretKey::random();
+
+        // Prepare transactions
+        let transaction_a = TransactionBuilder::new()
+            .with_body(TransactionBody::Raw(b"Hello, World! x1".to_vec()))
+            .sign(&validator)
+            .unwrap();
+
+        let transaction_b = TransactionBuilder::new()
+            .with_body(TransactionBody::Raw(b"Hello, World! x2".to_vec()))
+            .sign(&validator)
+            .unwrap();
+
+        // Prepare blocks
+        let block_a = BlockBuilder::build_root(&validator);
+
+        let block_b = BlockBuilder::chained(&block_a)
+            .add_transaction(transaction_a.clone())
+            .sign(&validator);
+
+        let block_c = BlockBuilder::chained(&block_b)
+            .add_transaction(transaction_b.clone())
+            .sign(&validator);
+
+        let block_c_alt = BlockBuilder::chained(&block_b).sign(&validator);
+
+        // Prepare indexes
+        let blocks_index = Arc::new(MemoryBlocksIndex::new());
+
+        let transactions_index = TransactionsFile::open(
+            path.join("transactions"),
+            blocks_index.clone()
+        ).await?;
+
+        // Push A, B and C
+        for block in [block_a, block_b.clone(), block_c] {
+            blocks_index.insert_block(block).await.map_err(TransactionsFileError::BlocksIndex)?;
+        }
+
+        assert!(transactions_index.has_transaction(&transaction_a.get_hash()).await?);
+        assert!(transactions_index.has_transaction(&transaction_b.get_hash()).await?);
+
+        // Truncate C
+        blocks_index.truncate_after(1).await.map_err(TransactionsFileError::BlocksIndex)?;
+        transactions_index.truncate_after(1).await?;
+
+        assert!(transactions_index.has_transaction(&transaction_a.get_hash()).await?);
+        assert!(!transactions_index.has_transaction(&transaction_b.get_hash()).await?);
+
+        // Push alternative C
+        blocks_index.insert_block(block_c_alt.clone()).await.map_err(TransactionsFileError::BlocksIndex)?;
+
+        assert!(transactions_index.has_transaction(&transaction_a.get_hash()).await?);
+        assert!(!transactions_index.has_transaction(&transaction_b.get_hash()).await?);
+
+        assert_eq!(transactions_index.get_transaction(&transaction_a.get_hash()).await?, Some((
+            transaction_a.clone(),
+            block_b.clone()
+        )));
+
+        // Push D with A again
+        let block_d = BlockBuilder::chained(&block_c_alt)
+            .add_transaction(transaction_a.clone())
+            .sign(&validator);
+
+        blocks_index.insert_block(block_d).await.map_err(TransactionsFileError::BlocksIndex)?;
+
+        assert_eq!(transactions_index.get_transaction(&transaction_a.get_hash()).await?, Some((
+            transaction_a.clone(),
+            block_b.clone()
+        )));
+
+        assert_eq!(transactions_index.get_duplicates().await?, vec![
+            TransactionDuplicate {
+                transaction: transaction_a.get_hash(),
+                first_block: 1,
+                duplicate_block: 3
+            }
+        ]);
+
+        // Truncate D
+        blocks_index.truncate_after(2).await.map_err(TransactionsFileError::BlocksIndex)?;
+        transactions_index.truncate_after(2).await?;
+
+        assert!(transactions_index.get_duplicates().await?.is_empty());
+
+        // Push D with nonces
+        let block_d = BlockBuilder::chained(&block_c_alt)
+            .add_transaction(TransactionBuilder::new()
+                .with_nonce(7)
+                .with_body(TransactionBody::Raw(b"Hello, World! x4".to_vec()))
+                .sign(&validator)
+                .unwrap())
+            .sign(&validator);
+
+        blocks_index.insert_block(block_d).await.map_err(TransactionsFileError::BlocksIndex)?;
+
+        assert_eq!(transactions_index.get_latest_nonce(&validator.public_key()).await?, Some(7));
+        assert_eq!(transactions_index.get_latest_nonce_before(&validator.public_key(), 3).await?, None);
+        assert_eq!(transactions_index.get_latest_nonce_before(&validator.public_key(), 4).await?, Some(7));
+
+        // Truncate D
+        blocks_index.truncate_after(2).await.map_err(TransactionsFileError::BlocksIndex)?;
+        transactions_index.truncate_after(2).await?;
+
+        assert_eq!(transactions_index.get_latest_nonce(&validator.public_key()).await?, None);
+
+        // Push D with a group message which lists
+        // its recipient twice
+        let recipient = SecretKey::random();
+
+        let body = GroupMessageTransactionBuilder::new()
+            .with_recipient(recipient.public_key())
+            .with_content(b"Hello, World!")
+            .build(&validator)
+            .unwrap();
+
+        let TransactionBody::GroupMessage { from, mut recipients, format, content } = body else {
+            unreachable!();
+        };
+
+        recipients.push(recipients[0].clone());
+
+        let group_message = TransactionBuilder::new()
+            .with_body(TransactionBody::GroupMessage { from, recipients, format, content })
+            .sign(&validator)
+            .unwrap();
+
+        let block_d = BlockBuilder::chained(&block_c_alt)
+            .add_transaction(group_message.clone())
+            .sign(&validator);
+
+        blocks_index.insert_block(block_d).await.map_err(TransactionsFileError::BlocksIndex)?;
+
+        assert_eq!(transactions_index.get_received_transactions(&recipient.public_key()).await?, vec![
+            group_message.get_hash()
+        ]);
+
+        // Reopen the index
+        let transactions_index = TransactionsFile::open(
+            path.join("transactions"),
+            blocks_index.clone()
+        ).await?;
+
+        assert_eq!(transactions_index.get_received_transactions(&recipient.public_key()).await?, vec![
+            group_message.get_hash()
+        ]);
+
+        // Truncate D
+        blocks_index.truncate_after(2).await.map_err(TransactionsFileError::BlocksIndex)?;
+        transactions_index.truncate_after(2).await?;
+
+        assert!(transactions_index.get_received_transactions(&recipient.public_key()).await?.is_empty());
+
+        // Push D with a reply to A
+        let reply = TransactionBuilder::new()
+            .with_reference(transaction_a.get_hash())
+            .with_body(TransactionBody::Raw(b"Hello, A!".to_vec()))
+            .sign(&validator)
+            .unwrap();
+
+        let block_d = BlockBuilder::chained(&block_c_alt)
+            .add_transaction(reply.clone())
+            .sign(&validator);
+
+        blocks_index.insert_block(block_d).await.map_err(TransactionsFileError::BlocksIndex)?;
+
+        assert_eq!(transactions_index.get_referencing_transactions(&transaction_a.get_hash()).await?, vec![
+            reply.get_hash()
+        ]);
+
+        // Truncate D
+        blocks_index.truncate_after(2).await.map_err(TransactionsFileError::BlocksIndex)?;
+        transactions_index.truncate_after(2).await?;
+
+        assert!(transactions_index.get_referencing_transactions(&transaction_a.get_hash()).await?.is_empty());
+
+        // Simulate indexing of D interrupted after
+        // its nonce was stored
+        let mut nonce_entry = TransactionsFile::<MemoryBlocksIndex>::key_hash(&validator.public_key())
+            .as_bytes()
+            .to_vec();
+
+        nonce_entry.extend_from_slice(&7u64.to_be_bytes());
+        nonce_entry.extend_from_slice(&3u64.to_be_bytes());
+
+        tokio::fs::write(path.join("transactions.nonces"), nonce_entry).await?;
+
+        // Reopen the index
+        let transactions_index = TransactionsFile::open(
+            path.join("transactions"),
+            blocks_index.clone()
+        ).await?;
+
+        assert_eq!(transactions_index.get_latest_nonce(&validator.public_key()).await?, None);
+
+        tokio::fs::remove_dir_all(&path).await?;
+
         Ok(())
     }
 }
@@ -0,0 +1,75 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Source of the current UTC time.
+///
+/// Used by blocks and transactions builders
+/// and validators instead of the system time
+/// to allow deterministic tests.
+pub trait Clock: std::fmt::Debug + Send + Sync {
+    /// Get current UTC timestamp (amount of seconds).
+    fn timestamp(&self) -> u64;
+}
+
+impl<T: Clock + ?Sized> Clock for Arc<T> {
+    #[inline]
+    fn timestamp(&self) -> u64 {
+        self.as_ref().timestamp()
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// Clock which uses the system time.
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    #[inline]
+    fn timestamp(&self) -> u64 {
+        hyperborealib::time::timestamp()
+    }
+}
+
+#[derive(Default, Debug)]
+/// Clock which returns manually set time.
+///
+/// ```
+/// use hyperchain::prelude::*;
+///
+/// let clock = MockClock::new(1000);
+///
+/// clock.advance(20);
+///
+/// assert_eq!(clock.timestamp(), 1020);
+/// ```
+pub struct MockClock {
+    timestamp: AtomicU64
+}
+
+impl MockClock {
+    #[inline]
+    /// Create new clock with given UTC timestamp.
+    pub fn new(timestamp: u64) -> Self {
+        Self {
+            timestamp: AtomicU64::new(timestamp)
+        }
+    }
+
+    #[inline]
+    /// Change clock's timestamp.
+    pub fn set(&self, timestamp: u64) {
+        self.timestamp.store(timestamp, Ordering::Release);
+    }
+
+    #[inline]
+    /// Move clock's timestamp forward.
+    pub fn advance(&self, seconds: u64) {
+        self.timestamp.fetch_add(seconds, Ordering::AcqRel);
+    }
+}
+
+impl Clock for MockClock {
+    #[inline]
+    fn timestamp(&self) -> u64 {
+        self.timestamp.load(Ordering::Acquire)
+    }
+}
@@ -0,0 +1,352 @@
+use std::str::FromStr;
+
+use serde_json::{json, Value as Json};
+
+use argon2::{Argon2, Algorithm, Version, Params};
+
+use chacha20poly1305::{XChaCha20Poly1305, XNonce, KeyInit};
+use chacha20poly1305::aead::{Aead, Payload};
+
+use hyperborealib::crypto::asymmetric::{SecretKey, PublicKey};
+use hyperborealib::crypto::encoding::base64;
+
+use hyperborealib::rest_api::{
+    AsJson,
+    AsJsonError
+};
+
+use crate::utils::random_bytes;
+
+use super::KeystoreError;
+
+const SALT_SIZE: usize = 16;
+const NONCE_SIZE: usize = 24;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// Purpose of the stored key.
+pub enum KeyRole {
+    /// Key used to sign blocks.
+    Authority,
+
+    /// Key used to sign transactions.
+    Author,
+
+    /// Application-specific role.
+    Other(String)
+}
+
+impl std::fmt::Display for KeyRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Authority => write!(f, "authority"),
+            Self::Author    => write!(f, "author"),
+            Self::Other(role) => write!(f, "{role}")
+        }
+    }
+}
+
+impl FromStr for KeyRole {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "authority" => Ok(Self::Authority),
+            "author"    => Ok(Self::Author),
+
+            role => Ok(Self::Other(role.to_string()))
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// Parameters of the argon2id password hashing.
+pub struct KdfParams {
+    /// Memory size in KiB.
+    ///
+    /// Default is 64 MiB.
+    pub memory_cost: u32,
+
+    /// Amount of iterations.
+    ///
+    /// Default is 3.
+    pub iterations: u32,
+
+    /// Degree of parallelism.
+    ///
+    /// Default is 1.
+    pub parallelism: u32
+}
+
+impl Default for KdfParams {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            memory_cost: 64 * 1024,
+            iterations: 3,
+            parallelism: 1
+        }
+    }
+}
+
+impl KdfParams {
+    /// Maximal memory size in KiB accepted
+    /// from the stored keys (4 GiB).
+    pub const MAX_MEMORY_COST: u32 = 4 * 1024 * 1024;
+
+    /// Maximal amount of iterations
+    /// accepted from the stored keys.
+    pub const MAX_ITERATIONS: u32 = 1024;
+
+    /// Maximal degree of parallelism
+    /// accepted from the stored keys.
+    pub const MAX_PARALLELISM: u32 = 64;
+
+    /// Derive encryption key from the password.
+    fn derive(&self, password: &[u8], salt: &[u8]) -> Result<[u8; 32], KeystoreError> {
+        let params = Params::new(self.memory_cost, self.iterations, self.parallelism, Some(32))
+            .map_err(KeystoreError::Kdf)?;
+
+        let mut key = [0; 32];
+
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password_into(password, salt, &mut key)
+            .map_err(KeystoreError::Kdf)?;
+
+        Ok(key)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// Public information about the stored key.
+pub struct KeyMetadata {
+    /// Public key of the stored secret key.
+    pub public_key: PublicKey,
+
+    /// Purpose of the key.
+    pub role: KeyRole,
+
+    /// Timestamp of the key creation.
+    pub created_at: u64
+}
+
+impl KeyMetadata {
+    /// Encode metadata to bytes authenticated
+    /// together with the encrypted secret key.
+    fn associated_data(&self) -> Vec<u8> {
+        let public_key = self.public_key.to_bytes();
+        let role = self.role.to_string();
+
+        let mut data = Vec::with_capacity(public_key.len() + role.len() + 16);
+
+        data.extend_from_slice(&public_key);
+        data.extend_from_slice(&(role.len() as u64).to_be_bytes());
+        data.extend_from_slice(role.as_bytes());
+        data.extend_from_slice(&self.created_at.to_be_bytes());
+
+        data
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// Secret key encrypted with a password.
+///
+/// Encryption key is derived from the password
+/// using argon2id and the secret key is encrypted
+/// using XChaCha20-Poly1305. Key's metadata is used
+/// as associated data so it can't be changed
+/// without breaking the decryption.
+pub struct EncryptedKey {
+    metadata: KeyMetadata,
+    kdf: KdfParams,
+    salt: Vec<u8>,
+    nonce: Vec<u8>,
+    ciphertext: Vec<u8>
+}
+
+impl EncryptedKey {
+    /// Encrypt secret key with the given password.
+    pub fn encrypt(
+        secret: &SecretKey,
+        password: impl AsRef<[u8]>,
+        role: KeyRole,
+        created_at: u64,
+        kdf: KdfParams
+    ) -> Result<Self, KeystoreError> {
+        let salt = random_bytes(SALT_SIZE);
+        let nonce = random_bytes(NONCE_SIZE);
+
+        let key = kdf.derive(password.as_ref(), &salt)?;
+        let secret_bytes = secret.to_bytes();
+
+        let metadata = KeyMetadata {
+            public_key: secret.public_key(),
+            role,
+            created_at
+        };
+
+        let payload = Payload {
+            msg: &secret_bytes[..],
+            aad: &metadata.associated_data()
+        };
+
+        let ciphertext = XChaCha20Poly1305::new(&key.into())
+            .encrypt(XNonce::from_slice(&nonce), payload)
+            .map_err(|_| KeystoreError::Encryption)?;
+
+        Ok(Self {
+            metadata,
+            kdf,
+            salt,
+            nonce,
+            ciphertext
+        })
+    }
+
+    /// Decrypt secret key with the given password.
+    pub fn decrypt(&self, password: impl AsRef<[u8]>) -> Result<SecretKey, KeystoreError> {
+        if self.nonce.len() != NONCE_SIZE {
+            return Err(KeystoreError::InvalidPassword);
+        }
+
+        let key = self.kdf.derive(password.as_ref(), &self.salt)?;
+
+        let payload = Payload {
+            msg: self.ciphertext.as_slice(),
+            aad: &self.metadata.associated_data()
+        };
+
+        // Fails if the metadata was changed as well
+        let secret = XChaCha20Poly1305::new(&key.into())
+            .decrypt(XNonce::from_slice(&self.nonce), payload)
+            .map_err(|_| KeystoreError::InvalidPassword)?;
+
+        let secret = SecretKey::from_bytes(&secret)
+            .map_err(|_| KeystoreError::InvalidPassword)?;
+
+        // Should never happen since the public key is authenticated
+        if secret.public_key() != self.metadata.public_key {
+            return Err(KeystoreError::PublicKeyMismatch);
+        }
+
+        Ok(secret)
+    }
+
+    #[inline]
+    /// Public information about the key.
+    pub fn metadata(&self) -> &KeyMetadata {
+        &self.metadata
+    }
+
+    #[inline]
+    /// Password hashing parameters.
+    pub fn kdf(&self) -> &KdfParams {
+        &self.kdf
+    }
+}
+
+impl AsJson for EncryptedKey {
+    fn to_json(&self) -> Result<Json, AsJsonError> {
+        Ok(json!({
+            "format": 1,
+            "metadata": {
+                "public_key": self.metadata.public_key.to_base64(),
+                "role": self.metadata.role.to_string(),
+                "created_at": self.metadata.created_at
+            },
+            "kdf": {
+                "algorithm": "argon2id",
+                "memory_cost": self.kdf.memory_cost,
+                "iterations": self.kdf.iterations,
+                "parallelism": self.kdf.parallelism,
+                "salt": base64::encode(&self.salt)
+            },
+            "cipher": {
+                "algorithm": "xchacha20-poly1305",
+                "nonce": base64::encode(&self.nonce),
+                "ciphertext": base64::encode(&self.ciphertext)
+            }
+        }))
+    }
+
+    fn from_json(json: &Json) -> Result<Self, AsJsonError> where Self: Sized {
+        let Some(format) = json.get("format").and_then(Json::as_u64) else {
+            return Err(AsJsonError::FieldNotFound("format"));
+        };
+
+        match format {
+            1 => {
+                let Some(metadata) = json.get("metadata") else {
+                    return Err(AsJsonError::FieldNotFound("metadata"));
+                };
+
+                let Some(kdf) = json.get("kdf") else {
+                    return Err(AsJsonError::FieldNotFound("kdf"));
+                };
+
+                let Some(cipher) = json.get("cipher") else {
+                    return Err(AsJsonError::FieldNotFound("cipher"));
+                };
+
+                if kdf.get("algorithm").and_then(Json::as_str) != Some("argon2id") {
+                    return Err(AsJsonError::FieldValueInvalid("kdf.algorithm"));
+                }
+
+                if cipher.get("algorithm").and_then(Json::as_str) != Some("xchacha20-poly1305") {
+                    return Err(AsJsonError::FieldValueInvalid("cipher.algorithm"));
+                }
+
+                // Bound the parameters so crafted files
+                // can't make the KDF use unlimited resources.
+                let get_u32 = |field: &'static str, name: &'static str, max: u32| {
+                    kdf.get(name)
+                        .and_then(Json::as_u64)
+                        .and_then(|value| u32::try_from(value).ok())
+                        .filter(|value| *value <= max)
+                        .ok_or(AsJsonError::FieldValueInvalid(field))
+                };
+
+                Ok(Self {
+                    metadata: KeyMetadata {
+                        public_key: metadata.get("public_key")
+                            .and_then(Json::as_str)
+                            .map(PublicKey::from_base64)
+                            .ok_or_else(|| AsJsonError::FieldValueInvalid("metadata.public_key"))??,
+
+                        role: metadata.get("role")
+                            .and_then(Json::as_str)
+                            .map(KeyRole::from_str)
+                            .ok_or_else(|| AsJsonError::FieldValueInvalid("metadata.role"))?
+                            .map_err(|err| AsJsonError::Other(err.into()))?,
+
+                        created_at: metadata.get("created_at")
+                            .and_then(Json::as_u64)
+                            .ok_or_else(|| AsJsonError::FieldValueInvalid("metadata.created_at"))?
+                    },
+
+                    kdf: KdfParams {
+                        memory_cost: get_u32("kdf.memory_cost", "memory_cost", KdfParams::MAX_MEMORY_COST)?,
+                        iterations: get_u32("kdf.iterations", "iterations", KdfParams::MAX_ITERATIONS)?,
+                        parallelism: get_u32("kdf.parallelism", "parallelism", KdfParams::MAX_PARALLELISM)?
+                    },
+
+                    salt: kdf.get("salt")
+                        .and_then(Json::as_str)
+                        .map(base64::decode)
+                        .ok_or_else(|| AsJsonError::FieldValueInvalid("kdf.salt"))??,
+
+                    nonce: cipher.get("nonce")
+                        .and_then(Json::as_str)
+                        .map(base64::decode)
+                        .ok_or_else(|| AsJsonError::FieldValueInvalid("cipher.nonce"))??,
+
+                    ciphertext: cipher.get("ciphertext")
+                        .and_then(Json::as_str)
+                        .map(base64::decode)
+                        .ok_or_else(|| AsJsonError::FieldValueInvalid("cipher.ciphertext"))??
+                })
+            }
+
+            version => Err(AsJsonError::InvalidStandard(version))
+        }
+    }
+}
@@ -0,0 +1,354 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use serde_json::{json, Value as Json};
+
+use hyperborealib::exports::tokio;
+
+use hyperborealib::crypto::asymmetric::SecretKey;
+
+use hyperborealib::rest_api::{
+    AsJson,
+    AsJsonError
+};
+
+use crate::clock::{Clock, SystemClock};
+
+mod encrypted_key;
+
+pub use encrypted_key::*;
+
+#[derive(Debug, thiserror::Error)]
+pub enum KeystoreError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error(transparent)]
+    Json(#[from] AsJsonError),
+
+    #[error(transparent)]
+    Serialize(#[from] serde_json::Error),
+
+    #[error("Failed to derive encryption key: {0}")]
+    Kdf(argon2::Error),
+
+    #[error("Password hashing task failed: {0}")]
+    Worker(#[from] tokio::task::JoinError),
+
+    #[error("Failed to encrypt secret key")]
+    Encryption,
+
+    #[error("Invalid password or modified key metadata")]
+    InvalidPassword,
+
+    #[error("Decrypted secret key doesn't match stored public key")]
+    PublicKeyMismatch,
+
+    #[error("Key with name '{0}' already exists")]
+    KeyExists(String),
+
+    #[error("Key with name '{0}' not found")]
+    KeyNotFound(String)
+}
+
+/// Password-encrypted storage of named secret keys.
+///
+/// All the keys are stored in a single JSON file
+/// which is readable only by its owner on unix systems.
+/// Each key is encrypted separately so they can
+/// have different passwords and can be moved
+/// between keystores.
+///
+/// Unlocked keys can be used to sign blocks and
+/// transactions and to build messages:
+///
+/// ```no_run
+/// use hyperchain::prelude::*;
+///
+/// async fn build_block() -> Result<Block, KeystoreError> {
+///     let mut keystore = Keystore::open("keystore.json").await?;
+///
+///     keystore.generate("validator", KeyRole::Authority, "password").await?;
+///
+///     let validator = keystore.unlock("validator", "password").await?;
+///
+///     Ok(BlockBuilder::new().sign(&validator))
+/// }
+/// ```
+pub struct Keystore {
+    path: PathBuf,
+    keys: BTreeMap<String, EncryptedKey>,
+    kdf: KdfParams
+}
+
+impl Keystore {
+    /// Open or create keystore file.
+    pub async fn open(path: impl Into<PathBuf>) -> Result<Self, KeystoreError> {
+        let path: PathBuf = path.into();
+
+        let mut keys = BTreeMap::new();
+
+        if path.exists() {
+            let keystore = tokio::fs::read(&path).await?;
+            let keystore = serde_json::from_slice::<Json>(&keystore)?;
+
+            let Some(format) = keystore.get("format").and_then(Json::as_u64) else {
+                return Err(AsJsonError::FieldNotFound("format").into());
+            };
+
+            if format != 1 {
+                return Err(AsJsonError::InvalidStandard(format).into());
+            }
+
+            let Some(stored) = keystore.get("keys").and_then(Json::as_object) else {
+                return Err(AsJsonError::FieldValueInvalid("keys").into());
+            };
+
+            for (name, key) in stored {
+                keys.insert(name.clone(), EncryptedKey::from_json(key)?);
+            }
+        } else if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() && !parent.exists() {
+                tokio::fs::create_dir_all(parent).await?;
+            }
+        }
+
+        Ok(Self {
+            path,
+            keys,
+            kdf: KdfParams::default()
+        })
+    }
+
+    #[inline]
+    /// Change password hashing parameters
+    /// used for newly stored keys.
+    pub fn with_kdf_params(mut self, kdf: KdfParams) -> Self {
+        self.kdf = kdf;
+
+        self
+    }
+
+    #[inline]
+    /// Iterate over names and metadata of the stored keys.
+    pub fn keys(&self) -> impl Iterator<Item = (&str, &KeyMetadata)> {
+        self.keys.iter()
+            .map(|(name, key)| (name.as_str(), key.metadata()))
+    }
+
+    #[inline]
+    /// Get metadata of the key with given name.
+    pub fn metadata(&self, name: impl AsRef<str>) -> Option<&KeyMetadata> {
+        self.keys.get(name.as_ref())
+            .map(EncryptedKey::metadata)
+    }
+
+    /// Encrypt and store the secret key.
+    ///
+    /// Password hashing is done in a blocking
+    /// task to not stall the async runtime.
+    pub async fn insert(
+        &mut self,
+        name: impl ToString,
+        secret: &SecretKey,
+        role: KeyRole,
+        password: impl AsRef<[u8]>
+    ) -> Result<(), KeystoreError> {
+        let secret = secret.clone();
+        let password = password.as_ref().to_vec();
+        let created_at = SystemClock.timestamp();
+        let kdf = self.kdf;
+
+        let key = tokio::task::spawn_blocking(move || {
+            EncryptedKey::encrypt(&secret, password, role, created_at, kdf)
+        }).await??;
+
+        self.import(name, key).await
+    }
+
+    /// Generate, encrypt and store new secret key.
+    pub async fn generate(
+        &mut self,
+        name: impl ToString,
+        role: KeyRole,
+        password: impl AsRef<[u8]>
+    ) -> Result<SecretKey, KeystoreError> {
+        let secret = SecretKey::random();
+
+        self.insert(name, &secret, role, password).await?;
+
+        Ok(secret)
+    }
+
+    /// Decrypt the key with given name.
+    ///
+    /// Password hashing is done in a blocking
+    /// task to not stall the async runtime.
+    pub async fn unlock(&self, name: impl AsRef<str>, password: impl AsRef<[u8]>) -> Result<SecretKey, KeystoreError> {
+        let name = name.as_ref();
+
+        let key = self.keys.get(name)
+            .ok_or_else(|| KeystoreError::KeyNotFound(name.to_string()))?
+            .clone();
+
+        let password = password.as_ref().to_vec();
+
+        tokio::task::spawn_blocking(move || key.decrypt(password)).await?
+    }
+
+    /// Remove the key with given name.
+    ///
+    /// Return `false` if there's no such key.
+    /// The key is kept if the keystore file
+    /// couldn't be updated.
+    pub async fn remove(&mut self, name: impl AsRef<str>) -> Result<bool, KeystoreError> {
+        let name = name.as_ref();
+
+        let Some(key) = self.keys.remove(name) else {
+            return Ok(false);
+        };
+
+        if let Err(err) = self.save().await {
+            self.keys.insert(name.to_string(), key);
+
+            return Err(err);
+        }
+
+        Ok(true)
+    }
+
+    #[inline]
+    /// Export encrypted key with given name.
+    ///
+    /// Exported key can be serialized with `AsJson`
+    /// and imported to another keystore.
+    pub fn export(&self, name: impl AsRef<str>) -> Option<EncryptedKey> {
+        self.keys.get(name.as_ref()).cloned()
+    }
+
+    /// Import encrypted key under given name.
+    ///
+    /// The key is not imported if the keystore
+    /// file couldn't be updated.
+    pub async fn import(&mut self, name: impl ToString, key: EncryptedKey) -> Result<(), KeystoreError> {
+        let name = name.to_string();
+
+        if self.keys.contains_key(&name) {
+            return Err(KeystoreError::KeyExists(name));
+        }
+
+        self.keys.insert(name.clone(), key);
+
+        if let Err(err) = self.save().await {
+            self.keys.remove(&name);
+
+            return Err(err);
+        }
+
+        Ok(())
+    }
+
+    async fn save(&self) -> Result<(), KeystoreError> {
+        let keys = self.keys.iter()
+            .map(|(name, key)| Ok::<_, AsJsonError>((name.clone(), key.to_json()?)))
+            .collect::<Result<serde_json::Map<_, _>, _>>()?;
+
+        let keystore = json!({
+            "format": 1,
+            "keys": keys
+        });
+
+        crate::utils::write_atomic_private(&self.path, serde_json::to_string_pretty(&keystore)?).await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::block::prelude::*;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn keystore() -> Result<(), KeystoreError> {
+        let dir = crate::utils::test_dir("keystore-test")?;
+        let path = dir.join("keystore.json");
+
+        // Make tests fast
+        let kdf = KdfParams {
+            memory_cost: 1024,
+            iterations: 1,
+            parallelism: 1
+        };
+
+        let mut keystore = Keystore::open(&path).await?
+            .with_kdf_params(kdf);
+
+        let validator = keystore.generate("validator", KeyRole::Authority, "password").await?;
+
+        assert!(matches!(keystore.generate("validator", KeyRole::Author, "password").await, Err(KeystoreError::KeyExists(_))));
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            assert_eq!(std::fs::metadata(&path)?.permissions().mode() & 0o777, 0o600);
+        }
+
+        // Reopen keystore
+        let mut keystore = Keystore::open(&path).await?
+            .with_kdf_params(kdf);
+
+        assert_eq!(keystore.metadata("validator").map(|metadata| &metadata.role), Some(&KeyRole::Authority));
+
+        assert!(matches!(keystore.unlock("validator", "wrong password").await, Err(KeystoreError::InvalidPassword)));
+        assert!(matches!(keystore.unlock("author", "password").await, Err(KeystoreError::KeyNotFound(_))));
+
+        let unlocked = keystore.unlock("validator", "password").await?;
+
+        assert_eq!(unlocked.public_key(), validator.public_key());
+        assert!(BlockBuilder::new().sign(&unlocked).validate().unwrap().is_valid());
+
+        // Export and import the key
+        let exported = keystore.export("validator").unwrap();
+        let exported = EncryptedKey::from_json(&exported.to_json()?)?;
+
+        keystore.import("validator-copy", exported.clone()).await?;
+
+        assert_eq!(keystore.unlock("validator-copy", "password").await?.public_key(), validator.public_key());
+
+        // Metadata can't be changed
+        let mut json = exported.to_json()?;
+
+        json["metadata"]["role"] = Json::from("author");
+
+        assert!(matches!(EncryptedKey::from_json(&json)?.decrypt("password"), Err(KeystoreError::InvalidPassword)));
+
+        // KDF parameters are bounded
+        let mut json = exported.to_json()?;
+
+        json["kdf"]["memory_cost"] = Json::from(u32::MAX);
+
+        assert!(EncryptedKey::from_json(&json).is_err());
+
+        assert!(keystore.remove("validator").await?);
+        assert!(!keystore.remove("validator").await?);
+
+        assert_eq!(keystore.keys().count(), 1);
+
+        // Keys are not changed if the file can't be saved
+        tokio::fs::remove_file(&path).await?;
+        tokio::fs::create_dir(&path).await?;
+
+        assert!(keystore.import("validator", exported).await.is_err());
+        assert!(keystore.remove("validator-copy").await.is_err());
+
+        assert!(keystore.metadata("validator").is_none());
+        assert!(keystore.metadata("validator-copy").is_some());
+
+        tokio::fs::remove_dir_all(&dir).await?;
+
+        Ok(())
+    }
+}
@@ -1,8 +1,16 @@
+pub mod clock;
+pub mod signer;
+pub mod keystore;
 pub mod block;
 pub mod blockchain;
 pub mod shard;
 
+pub(crate) mod utils;
+
 pub mod prelude {
+    pub use super::clock::*;
+    pub use super::signer::*;
+    pub use super::keystore::*;
     pub use super::block::prelude::*;
     pub use super::blockchain::prelude::*;
     pub use super::shard::prelude::*;
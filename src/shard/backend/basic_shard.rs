@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use crate::prelude::*;
 
@@ -22,6 +22,11 @@ pub enum BasicShardBackendError<A, B, C> {
 /// Staged transactions are stored in the RAM.
 /// It is recommended to write your own better implementation
 /// for high load applications.
+///
+/// Only transactions valid at the current time are staged,
+/// since they can be included to a new block at any moment.
+/// Not yet valid transactions are rejected and should be
+/// announced again once their validity window starts.
 pub struct BasicShardBackend<T> {
     /// Blockchain instance controlled by the shard's backend.
     blockchain: T,
@@ -55,6 +60,24 @@ impl<T: Blockchain> BasicShardBackend<T> {
             transaction_handler: None
         }
     }
+
+    /// Remove expired transactions from the staged pool.
+    ///
+    /// Expiration is checked using the blockchain's
+    /// validation context clock. Removed transactions
+    /// are returned.
+    pub fn evict_expired(&mut self) -> Vec<Transaction> {
+        let now = self.blockchain.validation_context().clock.timestamp();
+
+        let expired = self.staged_transactions.iter()
+            .filter(|(_, transaction)| transaction.is_expired_at(now))
+            .map(|(hash, _)| *hash)
+            .collect::<Vec<_>>();
+
+        expired.iter()
+            .flat_map(|hash| self.staged_transactions.remove(hash))
+            .collect()
+    }
 }
 
 #[async_trait::async_trait]
@@ -78,6 +101,8 @@ impl<T: Blockchain + Send + Sync> ShardBackend for BasicShardBackend<T> {
     }
 
     async fn get_staged_transactions(&mut self) -> Result<Vec<Hash>, Self::Error> {
+        self.evict_expired();
+
         Ok(self.staged_transactions.keys().copied().collect())
     }
 
@@ -104,15 +129,81 @@ impl<T: Blockchain + Send + Sync> ShardBackend for BasicShardBackend<T> {
     }
 
     async fn handle_block(&mut self, block: Block) -> Result<bool, Self::Error> {
+        // Reject blocks made for other blockchains.
+        let context = self.blockchain.validation_context();
+
+        if !context.is_chain_id_allowed(block.chain_id()) && !block.is_chain_genesis(&context) {
+            return Ok(false);
+        }
+
         // Validate block's authority before processing it.
         let is_authority = self.blockchain.authorities_index_ref()
-            .is_authority(block.validator()).await
+            .is_authority(block.authority()).await
             .map_err(BasicShardBackendError::AuthoritiesIndex)?;
 
         if !is_authority {
             return Ok(false);
         }
 
+        // Validate delegation certificate if the block
+        // is signed by an authority's session key.
+        if !matches!(block.check_delegation(&context), Ok(None)) {
+            return Ok(false);
+        }
+
+        // Block's timestamp is chosen by the signer so expired
+        // session keys could still sign blocks "in the past".
+        if let Some(delegation) = block.delegation() {
+            let (_, not_after) = delegation.time_bounds();
+
+            if not_after.is_some_and(|not_after| context.clock.timestamp() > not_after) {
+                return Ok(false);
+            }
+        }
+
+        // Validate block's linkage if its parent is known.
+        if let Some(parent_number) = block.number().checked_sub(1) {
+            let parent = self.blockchain.blocks_index_ref()
+                .get_block(parent_number).await
+                .map_err(BasicShardBackendError::BlocksIndex)?;
+
+            if let Some(parent) = parent {
+                if block.check_parent(&parent).is_some() {
+                    return Ok(false);
+                }
+            }
+        }
+
+        // Reject blocks with transactions which nonces don't
+        // follow the indexed ones if the blockchain requires them.
+        if context.require_nonces {
+            let mut nonces = HashMap::new();
+
+            for transaction in block.transactions() {
+                let Some(nonce) = transaction.nonce() else {
+                    return Ok(false);
+                };
+
+                let latest_nonce = match nonces.get(transaction.author()) {
+                    Some(latest_nonce) => Some(*latest_nonce),
+                    None => self.blockchain.transactions_index_ref()
+                        .get_latest_nonce_before(transaction.author(), block.number()).await
+                        .map_err(BasicShardBackendError::TransactionsIndex)?
+                };
+
+                let expected = match latest_nonce {
+                    Some(latest_nonce) => latest_nonce.checked_add(1),
+                    None => Some(0)
+                };
+
+                if expected != Some(nonce) {
+                    return Ok(false);
+                }
+
+                nonces.insert(transaction.author(), nonce);
+            }
+        }
+
         // Validate it if callback is specified.
         if let Some(validator) = &self.block_validator {
             if !validator(&block).await {
@@ -133,8 +224,10 @@ impl<T: Blockchain + Send + Sync> ShardBackend for BasicShardBackend<T> {
         }
 
         // If block has been indexed - remove transactions
-        // which were stabilized by it.
+        // which were stabilized by it or expired.
         if result {
+            self.evict_expired();
+
             let mut filtered_transactions = HashMap::with_capacity(self.staged_transactions.len());
 
             for (hash, transaction) in self.staged_transactions.drain() {
@@ -154,6 +247,26 @@ impl<T: Blockchain + Send + Sync> ShardBackend for BasicShardBackend<T> {
     }
 
     async fn handle_transaction(&mut self, transaction: Transaction) -> Result<bool, Self::Error> {
+        // Reject transactions made for other blockchains.
+        if !self.blockchain.validation_context().is_chain_id_allowed(transaction.chain_id()) {
+            return Ok(false);
+        }
+
+        // Reject expired and not yet valid transactions.
+        let now = self.blockchain.validation_context().clock.timestamp();
+
+        if transaction.is_expired_at(now) || transaction.valid_after().is_some_and(|valid_after| now < valid_after) {
+            return Ok(false);
+        }
+
+        // Reject custom transactions of unknown types
+        // or with invalid payloads.
+        let context = self.blockchain.validation_context();
+
+        if context.transaction_types.check(transaction.author(), transaction.body()).is_some() {
+            return Ok(false);
+        }
+
         // Check if transaction is already stabilized.
         let is_stabilized = self.blockchain.transactions_index_ref()
             .has_transaction(&transaction.get_hash()).await
@@ -163,6 +276,57 @@ impl<T: Blockchain + Send + Sync> ShardBackend for BasicShardBackend<T> {
             return Ok(false);
         }
 
+        // Reject transactions without nonces or with already
+        // used ones if the blockchain requires them.
+        if self.blockchain.validation_context().require_nonces {
+            let Some(nonce) = transaction.nonce() else {
+                return Ok(false);
+            };
+
+            let latest_nonce = self.blockchain.transactions_index_ref()
+                .get_latest_nonce(transaction.author()).await
+                .map_err(BasicShardBackendError::TransactionsIndex)?;
+
+            // Staged transactions of the same author take
+            // the following nonces, so the new one must
+            // continue their sequence without gaps.
+            let staged_nonces = self.staged_transactions.values()
+                .filter(|staged| staged.author() == transaction.author())
+                .flat_map(Transaction::nonce)
+                .collect::<HashSet<_>>();
+
+            let mut expected = match latest_nonce {
+                Some(latest_nonce) => latest_nonce.checked_add(1),
+                None => Some(0)
+            };
+
+            while let Some(staged) = expected.filter(|nonce| staged_nonces.contains(nonce)) {
+                expected = staged.checked_add(1);
+            }
+
+            if expected != Some(nonce) {
+                return Ok(false);
+            }
+        }
+
+        // Reject transactions referencing unknown ones
+        // if the blockchain requires known references.
+        if self.blockchain.validation_context().require_references {
+            for reference in transaction.references() {
+                if self.staged_transactions.contains_key(reference) {
+                    continue;
+                }
+
+                let is_known = self.blockchain.transactions_index_ref()
+                    .has_transaction(reference).await
+                    .map_err(BasicShardBackendError::TransactionsIndex)?;
+
+                if !is_known {
+                    return Ok(false);
+                }
+            }
+        }
+
         // Validate transaction if callback is specified.
         if let Some(validator) = &self.transaction_validator {
             if !validator(&transaction).await {
@@ -187,6 +351,35 @@ impl<T: Blockchain + Send + Sync> ShardBackend for BasicShardBackend<T> {
 
         Ok(false)
     }
+
+    async fn truncate_blocks(&mut self, number: u64) -> Result<Vec<Block>, Self::Error> {
+        // Remove blocks from the blocks index.
+        let removed_blocks = self.blockchain.blocks_index_ref()
+            .truncate_after(number).await
+            .map_err(BasicShardBackendError::BlocksIndex)?;
+
+        // Remove their transactions from the transactions index.
+        self.blockchain.transactions_index_ref()
+            .truncate_after(number).await
+            .map_err(BasicShardBackendError::TransactionsIndex)?;
+
+        // Return removed transactions back to the staged pool.
+        for block in &removed_blocks {
+            for transaction in block.transactions() {
+                self.staged_transactions.insert(
+                    transaction.get_hash(),
+                    transaction.clone()
+                );
+            }
+        }
+
+        Ok(removed_blocks)
+    }
+
+    #[inline]
+    fn validation_context(&self) -> ValidationContext {
+        self.blockchain.validation_context()
+    }
 }
 
 impl<T> ValidatableShardBackend for BasicShardBackend<T> {
@@ -212,3 +405,125 @@ impl<T> HandlableShardBackend for BasicShardBackend<T> {
         self.transaction_handler = Some(handler);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use hyperborealib::exports::tokio;
+    use hyperborealib::crypto::asymmetric::SecretKey;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn handle_transaction() -> Result<(), BasicShardBackendError<std::convert::Infallible, std::convert::Infallible, std::convert::Infallible>> {
+        let author = SecretKey::random();
+
+        let blockchain = MemoryBlockchain::in_memory([
+            author.public_key()
+        ]).with_validation_context(ValidationContext::default().with_clock(MockClock::new(1000)));
+
+        let mut backend = BasicShardBackend::new(blockchain);
+
+        let transaction = |valid_after: u64| {
+            TransactionBuilder::new()
+                .with_valid_after(valid_after)
+                .with_expires_at(3000)
+                .with_body(TransactionBody::Raw(valid_after.to_be_bytes().to_vec()))
+                .sign(&author)
+                .unwrap()
+        };
+
+        let valid = transaction(500);
+
+        backend.handle_transaction(valid.clone()).await?;
+        backend.handle_transaction(transaction(2000)).await?;
+
+        // Not yet valid transaction is not staged
+        assert_eq!(backend.get_staged_transactions().await?, vec![valid.get_hash()]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn handle_nonces() -> Result<(), BasicShardBackendError<std::convert::Infallible, std::convert::Infallible, std::convert::Infallible>> {
+        let author = SecretKey::random();
+
+        let blockchain = MemoryBlockchain::in_memory([
+            author.public_key()
+        ]).with_validation_context(ValidationContext::default().with_required_nonces(true));
+
+        let mut backend = BasicShardBackend::new(blockchain);
+
+        let transaction = |nonce: u64, body: &[u8]| {
+            TransactionBuilder::new()
+                .with_nonce(nonce)
+                .with_body(TransactionBody::Raw(body.to_vec()))
+                .sign(&author)
+                .unwrap()
+        };
+
+        let first = transaction(0, b"a");
+        let second = transaction(1, b"d");
+
+        backend.handle_transaction(first.clone()).await?;
+
+        // Already staged nonce
+        backend.handle_transaction(transaction(0, b"b")).await?;
+
+        // Nonce after a gap
+        backend.handle_transaction(transaction(2, b"c")).await?;
+
+        backend.handle_transaction(second.clone()).await?;
+
+        let staged = backend.get_staged_transactions().await?
+            .into_iter()
+            .collect::<HashSet<_>>();
+
+        assert_eq!(staged, HashSet::from([first.get_hash(), second.get_hash()]));
+
+        // Block's nonces must start from the indexed ones
+        let block = BlockBuilder::new()
+            .add_transaction(transaction(1, b"e"))
+            .sign(&author);
+
+        assert!(!backend.handle_block(block).await?);
+
+        let block = BlockBuilder::new()
+            .add_transaction(transaction(0, b"f"))
+            .add_transaction(transaction(1, b"g"))
+            .sign(&author);
+
+        assert!(backend.handle_block(block).await?);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn handle_expired_delegation() -> Result<(), BasicShardBackendError<std::convert::Infallible, std::convert::Infallible, std::convert::Infallible>> {
+        let authority = SecretKey::random();
+        let session = SecretKey::random();
+
+        let blockchain = MemoryBlockchain::in_memory([
+            authority.public_key()
+        ]).with_validation_context(ValidationContext::default().with_clock(MockClock::new(1000)));
+
+        let mut backend = BasicShardBackend::new(blockchain);
+
+        let block = |not_after: u64| {
+            let certificate = DelegationBuilder::new(session.public_key())
+                .with_time_bounds(0, not_after)
+                .sign(&authority);
+
+            BlockBuilder::new()
+                .with_clock(&MockClock::new(100))
+                .with_delegation(certificate)
+                .sign(&session)
+        };
+
+        // Certificate expired before the current time
+        assert!(!backend.handle_block(block(500)).await?);
+
+        assert!(backend.handle_block(block(2000)).await?);
+
+        Ok(())
+    }
+}
@@ -52,6 +52,24 @@ pub trait ShardBackend {
     ///
     /// Return true if the transaction was accepted.
     async fn handle_transaction(&mut self, transaction: Transaction) -> Result<bool, Self::Error>;
+
+    /// Remove all the blocks with numbers higher
+    /// than the given one from the blockchain.
+    ///
+    /// Transactions of the removed blocks should
+    /// be staged again.
+    ///
+    /// Return list of removed blocks.
+    async fn truncate_blocks(&mut self, number: u64) -> Result<Vec<Block>, Self::Error>;
+
+    /// Context used to validate blocks
+    /// and transactions.
+    ///
+    /// It should be the same as the one used
+    /// by the backend's blockchain.
+    fn validation_context(&self) -> ValidationContext {
+        ValidationContext::default()
+    }
 }
 
 pub(crate) type Validator<T> = Box<dyn Fn(&T) -> Pin<Box<dyn Future<Output = bool> + Send + Sync>> + Send + Sync>;
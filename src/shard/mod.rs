@@ -1,4 +1,5 @@
 use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use serde_json::Value as Json;
@@ -152,7 +153,12 @@ pub struct Shard<T: HttpClient, F: ShardBackend + Send + Sync> {
     subscribers: HashMap<ShardMember, ShardMemberStatus>,
 
     /// Shard options.
-    options: ShardOptions
+    options: ShardOptions,
+
+    /// Cache of verified signatures used if the backend's
+    /// validation context doesn't have one, since the same
+    /// blocks are usually received from many members.
+    signature_cache: Arc<SignatureCache>
 }
 
 impl<T: HttpClient, F: ShardBackend + Send + Sync> Shard<T, F> {
@@ -168,7 +174,8 @@ impl<T: HttpClient, F: ShardBackend + Send + Sync> Shard<T, F> {
             handled_transactions: HashSet::new(),
             subscriptions: HashMap::new(),
             subscribers: HashMap::new(),
-            options: ShardOptions::default()
+            options: ShardOptions::default(),
+            signature_cache: Arc::new(SignatureCache::default())
         }
     }
 
@@ -180,12 +187,58 @@ impl<T: HttpClient, F: ShardBackend + Send + Sync> Shard<T, F> {
         self
     }
 
+    #[inline]
+    /// Change cache of verified signatures used if the
+    /// backend's validation context doesn't have one.
+    ///
+    /// By default every shard has its own cache.
+    pub fn with_signature_cache(&mut self, cache: Arc<SignatureCache>) -> &mut Self {
+        self.signature_cache = cache;
+
+        self
+    }
+
     #[inline]
     /// Get reference to the shard's backend implementation
     pub fn backend_ref(&mut self) -> &mut F {
         &mut self.backend
     }
 
+    /// Get context used to validate incoming
+    /// blocks and transactions.
+    ///
+    /// Context is provided by the shard's backend
+    /// so it's the same as the one used by its blockchain.
+    fn validation_context(&self) -> ValidationContext {
+        let mut context = self.backend.validation_context();
+
+        if context.signature_cache.is_none() {
+            context.signature_cache = Some(self.signature_cache.clone());
+        }
+
+        context
+    }
+
+    /// Validate incoming block, verifying that it
+    /// follows its parent if the parent is known.
+    async fn validate_block(&mut self, block: &Block) -> Result<bool, ShardError<F::Error>> {
+        let parent = match block.number().checked_sub(1) {
+            Some(parent_number) => self.backend.get_block(parent_number).await
+                .map_err(ShardError::ShardBackend)?,
+
+            None => None
+        };
+
+        let context = self.validation_context();
+
+        let result = match &parent {
+            Some(parent) => block.validate_with_parent_and_context(parent, &context)?,
+            None => block.validate_with(&context)?
+        };
+
+        Ok(result.is_valid())
+    }
+
     async fn send(&self, member: &ShardMember, message: impl Into<ShardMessage>) -> Result<(), ShardError<F::Error>> {
         let message: ShardMessage = message.into();
 
@@ -533,7 +586,7 @@ impl<T: HttpClient, F: ShardBackend + Send + Sync> Shard<T, F> {
                                 // Handle head block.
                                 if let Some(head_block) = head_block.clone() {
                                     // Process it only if it's valid.
-                                    if head_block.validate()?.is_valid() {
+                                    if self.validate_block(&head_block).await? {
                                         self.backend.handle_block(head_block).await
                                             .map_err(ShardError::ShardBackend)?;
                                     }
@@ -542,7 +595,7 @@ impl<T: HttpClient, F: ShardBackend + Send + Sync> Shard<T, F> {
                                 // Handle tail block.
                                 if let Some(tail_block) = tail_block.clone() {
                                     // Process it only if it's valid.
-                                    if tail_block.validate()?.is_valid() {
+                                    if self.validate_block(&tail_block).await? {
                                         self.backend.handle_block(tail_block).await
                                             .map_err(ShardError::ShardBackend)?;
                                     }
@@ -715,7 +768,7 @@ impl<T: HttpClient, F: ShardBackend + Send + Sync> Shard<T, F> {
                                     }
 
                                     // Keep only valid ones.
-                                    if block.validate()?.is_valid() {
+                                    if self.validate_block(&block).await? {
                                         // Handle valid blocks individually.
                                         self.backend.handle_block(block.clone()).await
                                             .map_err(ShardError::ShardBackend)?;
@@ -783,6 +836,8 @@ impl<T: HttpClient, F: ShardBackend + Send + Sync> Shard<T, F> {
                                 // ordering while we would probably like to re-order them using
                                 // our own rules set.
 
+                                let context = self.validation_context();
+
                                 // Iterate over announced transactions.
                                 for transaction in transactions.drain(..) {
                                     // Skip already processed transactions.
@@ -793,7 +848,7 @@ impl<T: HttpClient, F: ShardBackend + Send + Sync> Shard<T, F> {
                                     }
 
                                     // Keep only valid ones.
-                                    if transaction.validate()?.is_valid() {
+                                    if transaction.validate_with(&context)?.is_valid() {
                                         // Handle valid blocks individually.
                                         self.backend.handle_transaction(transaction.clone()).await
                                             .map_err(ShardError::ShardBackend)?;
@@ -0,0 +1,52 @@
+use std::convert::Infallible;
+
+use hyperborealib::crypto::asymmetric::{SecretKey, PublicKey};
+
+use crate::block::hash::Hash;
+
+#[cfg(unix)]
+pub(crate) mod remote;
+
+#[cfg(unix)]
+pub use remote::*;
+
+#[async_trait::async_trait]
+/// This trait implementation should sign
+/// hashes of blocks and transactions.
+///
+/// Signer allows to keep secret keys outside
+/// of the current process, e.g. in a separate
+/// locked-down process or a hardware device.
+///
+/// ```
+/// use hyperchain::prelude::*;
+///
+/// async fn build_block<S: Signer>(signer: &S) -> Result<Block, S::Error> {
+///     BlockBuilder::new().sign_with(signer).await
+/// }
+/// ```
+pub trait Signer: Send + Sync {
+    type Error: std::error::Error + Send + Sync;
+
+    /// Get public key of the signer.
+    fn public_key(&self) -> PublicKey;
+
+    /// Sign given hash.
+    async fn sign(&self, hash: &Hash) -> Result<Vec<u8>, Self::Error>;
+}
+
+#[async_trait::async_trait]
+/// In-memory signer.
+impl Signer for SecretKey {
+    type Error = Infallible;
+
+    #[inline]
+    fn public_key(&self) -> PublicKey {
+        SecretKey::public_key(self)
+    }
+
+    #[inline]
+    async fn sign(&self, hash: &Hash) -> Result<Vec<u8>, Self::Error> {
+        Ok(self.create_signature(hash.as_bytes()))
+    }
+}
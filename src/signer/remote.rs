@@ -0,0 +1,338 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use std::os::unix::fs::{PermissionsExt, DirBuilderExt};
+
+use hyperborealib::exports::tokio;
+
+use tokio::net::{UnixListener, UnixStream};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+use hyperborealib::crypto::Error as CryptographyError;
+use hyperborealib::crypto::utils::safe_random_u64;
+
+use super::*;
+
+const REQUEST_PUBLIC_KEY: u8 = 0;
+const REQUEST_SIGN: u8 = 1;
+
+const RESPONSE_OK: u8 = 0;
+const RESPONSE_ERROR: u8 = 1;
+
+/// Maximal size of the response payload.
+const MAX_RESPONSE_SIZE: u64 = 64 * 1024;
+
+/// Default time given to a connection
+/// to send its request and read the response.
+const DEFAULT_CONNECTION_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Time to wait before accepting new connections
+/// after a failure (e.g. too many open files).
+const ACCEPT_ERROR_DELAY: Duration = Duration::from_millis(100);
+
+#[derive(Debug, thiserror::Error)]
+pub enum RemoteSignerError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error("Remote signer returned invalid public key: {0}")]
+    InvalidPublicKey(CryptographyError),
+
+    #[error("Remote signer returned invalid signature")]
+    InvalidSignature,
+
+    #[error("Remote signer returned too large response: {0} bytes")]
+    ResponseTooLarge(u64),
+
+    #[error("Remote signer returned unknown response status: {0}")]
+    UnknownStatus(u8),
+
+    #[error("Remote signer rejected the request: {0}")]
+    Rejected(String)
+}
+
+#[derive(Debug, Clone)]
+/// Signer which sends hashes to another process
+/// over a local (unix) socket.
+///
+/// Secret key is stored only in the remote process
+/// which is served by the `RemoteSignerServer`.
+///
+/// Protocol:
+///
+/// - Request: `[u8 kind]`, followed by `[32 bytes hash]`
+///   for the sign request.
+/// - Response: `[u8 status][u64 length][payload]`
+///   where payload is either public key bytes,
+///   signature or error message.
+pub struct RemoteSigner {
+    path: PathBuf,
+    public_key: PublicKey
+}
+
+impl RemoteSigner {
+    /// Connect to the remote signer and
+    /// request its public key.
+    pub async fn connect(path: impl Into<PathBuf>) -> Result<Self, RemoteSignerError> {
+        let path = path.into();
+
+        let mut stream = UnixStream::connect(&path).await?;
+
+        stream.write_u8(REQUEST_PUBLIC_KEY).await?;
+
+        let public_key = read_response(&mut stream).await?;
+
+        let public_key = PublicKey::from_bytes(&public_key)
+            .map_err(RemoteSignerError::InvalidPublicKey)?;
+
+        Ok(Self {
+            path,
+            public_key
+        })
+    }
+
+    #[inline]
+    /// Path to the remote signer's socket.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+#[async_trait::async_trait]
+impl Signer for RemoteSigner {
+    type Error = RemoteSignerError;
+
+    #[inline]
+    fn public_key(&self) -> PublicKey {
+        self.public_key.clone()
+    }
+
+    async fn sign(&self, hash: &Hash) -> Result<Vec<u8>, Self::Error> {
+        let mut stream = UnixStream::connect(&self.path).await?;
+
+        stream.write_u8(REQUEST_SIGN).await?;
+        stream.write_all(&hash.as_bytes()).await?;
+
+        let sign = read_response(&mut stream).await?;
+
+        // Don't trust the remote process
+        match self.public_key.verify_signature(hash.as_bytes(), &sign) {
+            Ok(true) => Ok(sign),
+            _ => Err(RemoteSignerError::InvalidSignature)
+        }
+    }
+}
+
+/// Server of the `RemoteSigner` protocol.
+///
+/// Should be run in a separate process
+/// which stores the secret key.
+///
+/// Anyone who can connect to the socket can request
+/// signatures, so the socket file is made accessible
+/// only by its owner when bound. Its parent directory
+/// should not be writable by other users either.
+pub struct RemoteSignerServer<S: Signer> {
+    listener: UnixListener,
+    signer: Arc<S>,
+    timeout: Duration,
+    error_handler: Option<Box<dyn Fn(&std::io::Error) + Send + Sync>>
+}
+
+impl<S: Signer + 'static> RemoteSignerServer<S> {
+    /// Bind server to the given socket path.
+    ///
+    /// The socket is bound in a temporary directory
+    /// accessible only by its owner, its permissions are
+    /// set to `0600` and then it's linked to the given path.
+    /// This way other users can't connect to the socket
+    /// before its permissions are restricted.
+    pub fn bind(path: impl AsRef<Path>, signer: S) -> Result<Self, std::io::Error> {
+        let path = path.as_ref();
+
+        let mut bind_dir = path.as_os_str().to_owned();
+
+        bind_dir.push(format!(".{:x}.bind", safe_random_u64()));
+
+        let bind_dir = PathBuf::from(bind_dir);
+        let bind_path = bind_dir.join("socket");
+
+        std::fs::DirBuilder::new()
+            .mode(0o700)
+            .create(&bind_dir)?;
+
+        let listener = UnixListener::bind(&bind_path).and_then(|listener| {
+            std::fs::set_permissions(&bind_path, std::fs::Permissions::from_mode(0o600))?;
+
+            // Unlike renaming, linking fails if the path
+            // is already taken, same as binding to it.
+            std::fs::hard_link(&bind_path, path)?;
+
+            Ok(listener)
+        });
+
+        let _ = std::fs::remove_file(&bind_path);
+        let _ = std::fs::remove_dir(&bind_dir);
+
+        Ok(Self {
+            listener: listener?,
+            signer: Arc::new(signer),
+            timeout: DEFAULT_CONNECTION_TIMEOUT,
+            error_handler: None
+        })
+    }
+
+    #[inline]
+    /// Change time given to a connection to send
+    /// its request and read the response.
+    ///
+    /// Default is 5 seconds.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+
+        self
+    }
+
+    #[inline]
+    /// Set callback called when the server
+    /// fails to accept a connection.
+    pub fn with_error_handler(mut self, handler: impl Fn(&std::io::Error) + Send + Sync + 'static) -> Self {
+        self.error_handler = Some(Box::new(handler));
+
+        self
+    }
+
+    /// Accept incoming connections.
+    ///
+    /// Each connection is handled in a separate task
+    /// and closed if not finished within the timeout,
+    /// so idle clients can't block the server.
+    ///
+    /// Failures to accept a connection are reported
+    /// to the error handler and don't stop the server.
+    pub async fn serve(&self) {
+        loop {
+            let mut stream = match self.listener.accept().await {
+                Ok((stream, _)) => stream,
+
+                Err(err) => {
+                    if let Some(handler) = &self.error_handler {
+                        handler(&err);
+                    }
+
+                    tokio::time::sleep(ACCEPT_ERROR_DELAY).await;
+
+                    continue;
+                }
+            };
+
+            let signer = self.signer.clone();
+            let timeout = self.timeout;
+
+            tokio::spawn(async move {
+                // Broken connections shouldn't stop the server
+                let _ = tokio::time::timeout(timeout, handle_request(signer.as_ref(), &mut stream)).await;
+            });
+        }
+    }
+
+    #[inline]
+    /// Handle requests from the given connection.
+    pub async fn handle(&self, stream: &mut UnixStream) -> Result<(), std::io::Error> {
+        handle_request(self.signer.as_ref(), stream).await
+    }
+}
+
+async fn handle_request(signer: &impl Signer, stream: &mut UnixStream) -> Result<(), std::io::Error> {
+    match stream.read_u8().await? {
+        REQUEST_PUBLIC_KEY => {
+            let public_key = signer.public_key()
+                .to_bytes()
+                .to_vec();
+
+            write_response(stream, RESPONSE_OK, &public_key).await
+        }
+
+        REQUEST_SIGN => {
+            let mut hash = [0; 32];
+
+            stream.read_exact(&mut hash).await?;
+
+            match signer.sign(&Hash::from_bytes(hash)).await {
+                Ok(sign) => write_response(stream, RESPONSE_OK, &sign).await,
+                Err(err) => write_response(stream, RESPONSE_ERROR, err.to_string().as_bytes()).await
+            }
+        }
+
+        kind => write_response(stream, RESPONSE_ERROR, format!("Unknown request: {kind}").as_bytes()).await
+    }
+}
+
+async fn write_response(stream: &mut UnixStream, status: u8, payload: &[u8]) -> Result<(), std::io::Error> {
+    stream.write_u8(status).await?;
+    stream.write_u64(payload.len() as u64).await?;
+    stream.write_all(payload).await?;
+
+    stream.flush().await
+}
+
+async fn read_response(stream: &mut UnixStream) -> Result<Vec<u8>, RemoteSignerError> {
+    let status = stream.read_u8().await?;
+    let length = stream.read_u64().await?;
+
+    if length > MAX_RESPONSE_SIZE {
+        return Err(RemoteSignerError::ResponseTooLarge(length));
+    }
+
+    let mut payload = vec![0; length as usize];
+
+    stream.read_exact(&mut payload).await?;
+
+    match status {
+        RESPONSE_OK => Ok(payload),
+        RESPONSE_ERROR => Err(RemoteSignerError::Rejected(String::from_utf8_lossy(&payload).to_string())),
+
+        status => Err(RemoteSignerError::UnknownStatus(status))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn sign() -> Result<(), RemoteSignerError> {
+        let dir = crate::utils::test_dir("remote-signer-test")?;
+        let path = dir.join("signer.sock");
+
+        let secret = SecretKey::random();
+
+        let server = RemoteSignerServer::bind(&path, secret.clone())?
+            .with_timeout(Duration::from_millis(100));
+
+        assert_eq!(std::fs::metadata(&path)?.permissions().mode() & 0o777, 0o600);
+
+        // Socket path can't be taken twice
+        assert!(RemoteSignerServer::bind(&path, secret.clone()).is_err());
+
+        tokio::spawn(async move {
+            server.serve().await
+        });
+
+        // Idle connection doesn't block other clients
+        let _idle = UnixStream::connect(&path).await?;
+
+        let signer = RemoteSigner::connect(&path).await?;
+
+        assert_eq!(signer.public_key(), secret.public_key());
+
+        let hash = Hash::hash_slice(b"Hello, World!");
+        let sign = Signer::sign(&signer, &hash).await?;
+
+        assert!(secret.public_key().verify_signature(hash.as_bytes(), &sign).unwrap());
+
+        tokio::fs::remove_dir_all(&dir).await?;
+
+        Ok(())
+    }
+}
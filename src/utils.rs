@@ -0,0 +1,101 @@
+use std::path::{Path, PathBuf};
+
+use hyperborealib::exports::tokio;
+
+use tokio::io::AsyncWriteExt;
+
+use chacha20poly1305::aead::OsRng;
+use chacha20poly1305::aead::rand_core::RngCore;
+
+/// Generate random bytes using the operating
+/// system's cryptographically secure RNG.
+///
+/// Used for keys, salts and nonces.
+pub(crate) fn random_bytes(size: usize) -> Vec<u8> {
+    let mut bytes = vec![0; size];
+
+    OsRng.fill_bytes(&mut bytes);
+
+    bytes
+}
+
+/// Get path of the temporary file used
+/// to atomically write the given file.
+fn temp_path(path: &Path) -> PathBuf {
+    let mut temp_path = path.as_os_str().to_os_string();
+
+    temp_path.push(".tmp");
+
+    PathBuf::from(temp_path)
+}
+
+#[inline]
+/// Atomically replace content of the file.
+///
+/// Content is written and synced to a temporary
+/// file which is then renamed to the given path,
+/// so the file is never left partially written.
+pub(crate) async fn write_atomic(path: &Path, content: impl AsRef<[u8]>) -> std::io::Result<()> {
+    write_temp_and_rename(path, content.as_ref(), false).await
+}
+
+#[inline]
+/// Atomically replace content of the file
+/// which should be accessible only by its owner.
+///
+/// Same as `write_atomic`, but on unix systems the
+/// temporary file is created with `0600` permissions,
+/// so the content is never readable by other users.
+pub(crate) async fn write_atomic_private(path: &Path, content: impl AsRef<[u8]>) -> std::io::Result<()> {
+    write_temp_and_rename(path, content.as_ref(), true).await
+}
+
+#[cfg_attr(not(unix), allow(unused_variables))]
+/// Write content to the temporary file, optionally
+/// accessible only by its owner, and rename it.
+async fn write_temp_and_rename(path: &Path, content: &[u8], private: bool) -> std::io::Result<()> {
+    let temp_path = temp_path(path);
+
+    // Remove leftovers of an interrupted write
+    // so the file is created with our permissions.
+    if temp_path.exists() {
+        tokio::fs::remove_file(&temp_path).await?;
+    }
+
+    let mut options = tokio::fs::OpenOptions::new();
+
+    options.write(true).create_new(true);
+
+    #[cfg(unix)]
+    if private {
+        options.mode(0o600);
+    }
+
+    let mut file = options.open(&temp_path).await?;
+
+    // Sync the content before renaming so the file
+    // is not left empty after a power loss.
+    file.write_all(content).await?;
+    file.sync_all().await?;
+
+    drop(file);
+
+    tokio::fs::rename(&temp_path, path).await
+}
+
+#[cfg(test)]
+/// Create new temporary directory for a test.
+///
+/// Directory name is unique for every call so
+/// tests running in parallel don't share files.
+pub(crate) fn test_dir(name: &str) -> std::io::Result<PathBuf> {
+    let path = std::env::temp_dir().join(format!(
+        ".hyperchain.{name}-{}-{:x}",
+        std::process::id(),
+        hyperborealib::crypto::utils::safe_random_u64()
+    ));
+
+    std::fs::create_dir_all(&path)?;
+
+    Ok(path)
+}